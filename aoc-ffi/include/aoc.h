@@ -0,0 +1,52 @@
+/* Generated from src/lib.rs by `make header`, do not edit */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// The answer was written to `out`
+#define AOC_OK 0
+
+// `day` or `part` is out of range, or a pointer is null
+#define AOC_ERR_ARGUMENT 1
+
+// The day's binary isn't built or couldn't be started
+#define AOC_ERR_UNAVAILABLE 2
+
+// The day panicked or exited with an error on this input
+#define AOC_ERR_SOLVER 3
+
+// The day ran but printed no answer for the part
+#define AOC_ERR_NO_ANSWER 4
+
+// `out` is too small for the answer and its terminating NUL
+#define AOC_ERR_BUFFER 5
+
+// Something went wrong inside this library itself
+#define AOC_ERR_INTERNAL 6
+
+// Solves `part` (1 or 2) of `day` (1 to 25) for the `len` bytes of puzzle
+// input at `input`, writing the answer as a NUL terminated string into the
+// `out_len` bytes at `out`. Returns `AOC_OK` or one of the `AOC_ERR_` codes.
+//
+// # Safety
+// `input` must point to `len` readable bytes and `out` to `out_len`
+// writable ones. Day binaries are looked for next to this library, or in
+// the directory named by `AOC_BIN_DIR` when it is set.
+int32_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input, size_t len, char *out, size_t out_len);
+
+// Describes why the last failing `aoc_solve` on this thread failed, empty
+// if none has. Valid until the next failing call on the same thread.
+const char *aoc_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,105 @@
+//! Prints the C header for the `pub const`s and `extern "C"` functions in
+//! src/lib.rs, keeping their doc comments. Only the one-line forms used
+//! there are understood, which is all this crate needs.
+//!
+//! `make -C aoc-ffi header` writes it to include/aoc.h, which is committed.
+
+fn main() {
+    print!("{}", header(include_str!("../lib.rs")));
+}
+
+fn header(source: &str) -> String {
+    let mut out = String::from("/* Generated from src/lib.rs by `make header`, do not edit */\n\n");
+    out.push_str("#ifndef AOC_H\n#define AOC_H\n\n#include <stddef.h>\n#include <stdint.h>\n\n");
+    out.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n");
+
+    let mut docs: Vec<&str> = vec!();
+    for line in source.lines() {
+        let line = line.trim();
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.strip_prefix(' ').unwrap_or(doc));
+            continue;
+        }
+        if line.starts_with("#[") {
+            continue;
+        }
+
+        let item = match line.strip_prefix("pub const ") {
+            // NAME: type = value;
+            Some(constant) => {
+                let (name, rest) = constant.split_once(':').expect("const without a type");
+                let value = rest.split_once('=').expect("const without a value").1.trim().trim_end_matches(';');
+                Some(format!("#define {} {}", name.trim(), value))
+            }
+            None => line
+                .strip_prefix("pub unsafe extern \"C\" fn ")
+                .or_else(|| line.strip_prefix("pub extern \"C\" fn "))
+                .map(function_prototype),
+        };
+
+        if let Some(item) = item {
+            out.push('\n');
+            for doc in docs.iter() {
+                out.push_str(&format!("// {}\n", doc).replace("// \n", "//\n"));
+            }
+            out.push_str(&item);
+            out.push('\n');
+        }
+        docs.clear();
+    }
+
+    out.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif\n");
+    out
+}
+
+/// `name(arg: type, ...) -> type {` as a C prototype
+fn function_prototype(function: &str) -> String {
+    let (name, rest) = function.split_once('(').expect("function without arguments");
+    let (args, rest) = rest.split_once(')').expect("unclosed argument list");
+    let ret = match rest.trim().trim_end_matches('{').trim().strip_prefix("->") {
+        Some(ret) => c_type(ret.trim()),
+        None => "void".to_string(),
+    };
+    let args: Vec<String> = args
+        .split(',')
+        .map(|arg| arg.trim())
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| {
+            let (name, ty) = arg.split_once(':').expect("argument without a type");
+            let ty = c_type(ty.trim());
+            match ty.ends_with('*') {
+                true => format!("{}{}", ty, name.trim()),
+                false => format!("{} {}", ty, name.trim()),
+            }
+        })
+        .collect();
+    let args = if args.is_empty() { "void".to_string() } else { args.join(", ") };
+    match ret.ends_with('*') {
+        true => format!("{}{}({});", ret, name, args),
+        false => format!("{} {}({});", ret, name, args),
+    }
+}
+
+fn c_type(ty: &str) -> String {
+    if let Some(pointee) = ty.strip_prefix("*const ") {
+        return format!("const {} *", c_type(pointee));
+    }
+    if let Some(pointee) = ty.strip_prefix("*mut ") {
+        return format!("{} *", c_type(pointee));
+    }
+    match ty {
+        "u8" => "uint8_t",
+        "u16" => "uint16_t",
+        "u32" => "uint32_t",
+        "u64" => "uint64_t",
+        "i8" => "int8_t",
+        "i16" => "int16_t",
+        "i32" => "int32_t",
+        "i64" => "int64_t",
+        "usize" => "size_t",
+        "isize" => "ptrdiff_t",
+        "c_char" => "char",
+        _ => panic!("no C type for {}", ty),
+    }
+    .to_string()
+}
@@ -0,0 +1,151 @@
+//! C ABI over the day solvers, for test harnesses outside Rust. The
+//! matching header is include/aoc.h, generated from this file by
+//! src/bin/aoc-ffi-header.rs; run `make -C aoc-ffi header` after changing it.
+//!
+//! Each call runs the day's binary over the given input, the same way
+//! `aoc run` does, so the days stay plain programs and a day that crashes
+//! can't take the caller down with it.
+
+use std::cell::RefCell;
+use std::env;
+use std::ffi::{c_char, CString};
+use std::io::Write;
+use std::panic;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::slice;
+use std::thread;
+
+use aoc::scrape;
+
+/// The answer was written to `out`
+pub const AOC_OK: i32 = 0;
+/// `day` or `part` is out of range, or a pointer is null
+pub const AOC_ERR_ARGUMENT: i32 = 1;
+/// The day's binary isn't built or couldn't be started
+pub const AOC_ERR_UNAVAILABLE: i32 = 2;
+/// The day panicked or exited with an error on this input
+pub const AOC_ERR_SOLVER: i32 = 3;
+/// The day ran but printed no answer for the part
+pub const AOC_ERR_NO_ANSWER: i32 = 4;
+/// `out` is too small for the answer and its terminating NUL
+pub const AOC_ERR_BUFFER: i32 = 5;
+/// Something went wrong inside this library itself
+pub const AOC_ERR_INTERNAL: i32 = 6;
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Solves `part` (1 or 2) of `day` (1 to 25) for the `len` bytes of puzzle
+/// input at `input`, writing the answer as a NUL terminated string into the
+/// `out_len` bytes at `out`. Returns `AOC_OK` or one of the `AOC_ERR_` codes.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `out` to `out_len`
+/// writable ones. Day binaries are looked for next to this library, or in
+/// the directory named by `AOC_BIN_DIR` when it is set.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u32, part: u32, input: *const u8, len: usize, out: *mut c_char, out_len: usize) -> i32 {
+    if (input.is_null() && len > 0) || out.is_null() {
+        return fail(AOC_ERR_ARGUMENT, "null pointer passed to aoc_solve".to_string());
+    }
+    let input: &[u8] = if len == 0 { &[] } else { slice::from_raw_parts(input, len) };
+
+    // nothing may unwind into C, so any of our own panics become a code
+    let answer = match panic::catch_unwind(|| solve(day, part, input)) {
+        Ok(Ok(answer)) => answer,
+        Ok(Err((code, msg))) => return fail(code, msg),
+        Err(_) => return fail(AOC_ERR_INTERNAL, "aoc_solve panicked".to_string()),
+    };
+
+    let bytes = answer.as_bytes();
+    if bytes.len() >= out_len {
+        return fail(AOC_ERR_BUFFER, format!("answer {} needs {} bytes, out has {}", answer, bytes.len() + 1, out_len));
+    }
+    let out = slice::from_raw_parts_mut(out as *mut u8, out_len);
+    out[..bytes.len()].copy_from_slice(bytes);
+    out[bytes.len()] = 0;
+    AOC_OK
+}
+
+/// Describes why the last failing `aoc_solve` on this thread failed, empty
+/// if none has. Valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn aoc_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| error.borrow().as_ptr())
+}
+
+fn fail(code: i32, msg: String) -> i32 {
+    let msg = CString::new(msg.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|error| *error.borrow_mut() = msg);
+    code
+}
+
+fn solve(day: u32, part: u32, input: &[u8]) -> Result<String, (i32, String)> {
+    if !(1..=25).contains(&day) || !(1..=2).contains(&part) {
+        return Err((AOC_ERR_ARGUMENT, format!("there is no part {} of day {}", part, day)));
+    }
+    let dir = bin_dir().ok_or((AOC_ERR_UNAVAILABLE, "can't tell where the day binaries are, set AOC_BIN_DIR".to_string()))?;
+    let binary = dir.join(format!("day-{}{}", day, env::consts::EXE_SUFFIX));
+    if !binary.is_file() {
+        return Err((AOC_ERR_UNAVAILABLE, format!("{} isn't built", binary.display())));
+    }
+
+    let mut child = Command::new(&binary)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| (AOC_ERR_UNAVAILABLE, format!("starting {}: {}", binary.display(), e)))?;
+
+    // fed on the side, as a day may print before it has read everything
+    let mut stdin = child.stdin.take().unwrap();
+    let input = input.to_vec();
+    let feeder = thread::spawn(move || {
+        let _ = stdin.write_all(&input);
+    });
+    let output = child.wait_with_output().map_err(|e| (AOC_ERR_INTERNAL, format!("waiting on {}: {}", binary.display(), e)))?;
+    let _ = feeder.join();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = scrape::panic_message(&stderr).unwrap_or_else(|| output.status.to_string());
+        return Err((AOC_ERR_SOLVER, format!("day-{} failed: {}", day, reason)));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    scrape::parse_answers(&stdout)
+        .get(part as usize - 1)
+        .cloned()
+        .flatten()
+        .ok_or((AOC_ERR_NO_ANSWER, format!("day-{} printed no answer for part {}", day, part)))
+}
+
+/// `AOC_BIN_DIR`, or else the directory this library was loaded from,
+/// which for a cargo build is the target directory holding the days
+fn bin_dir() -> Option<PathBuf> {
+    match env::var_os("AOC_BIN_DIR") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => library_dir(),
+    }
+}
+
+#[cfg(unix)]
+fn library_dir() -> Option<PathBuf> {
+    use std::ffi::{CStr, OsStr};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    let mut info: libc::Dl_info = unsafe { std::mem::zeroed() };
+    let found = unsafe { libc::dladdr(aoc_solve as *const libc::c_void, &mut info) };
+    if found == 0 || info.dli_fname.is_null() {
+        return None;
+    }
+    let path = unsafe { CStr::from_ptr(info.dli_fname) };
+    Path::new(OsStr::from_bytes(path.to_bytes())).parent().filter(|dir| !dir.as_os_str().is_empty()).map(Path::to_path_buf)
+}
+
+#[cfg(not(unix))]
+fn library_dir() -> Option<PathBuf> {
+    None
+}
@@ -0,0 +1,75 @@
+/*
+ * Links against libaoc_ffi and checks a day's answers through the C ABI,
+ * along with the error codes for bad calls.
+ *
+ *   solve <day> <input> <part 1 answer> <part 2 answer>
+ *
+ * Run by `make test` in aoc-ffi/, exits non-zero on the first failure.
+ */
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void check(int ok, const char *what) {
+    printf("%s  %s\n", ok ? "ok  " : "FAIL", what);
+    if (!ok) {
+        printf("      last error: %s\n", aoc_last_error());
+        failures++;
+    }
+}
+
+static char *read_file(const char *path, size_t *len) {
+    FILE *file = fopen(path, "rb");
+    if (!file) {
+        perror(path);
+        exit(2);
+    }
+    fseek(file, 0, SEEK_END);
+    *len = (size_t)ftell(file);
+    rewind(file);
+    char *text = malloc(*len);
+    if (!text || fread(text, 1, *len, file) != *len) {
+        perror(path);
+        exit(2);
+    }
+    fclose(file);
+    return text;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 5) {
+        fprintf(stderr, "usage: %s <day> <input> <part 1 answer> <part 2 answer>\n", argv[0]);
+        return 2;
+    }
+    uint32_t day = (uint32_t)atoi(argv[1]);
+    size_t len;
+    char *input = read_file(argv[2], &len);
+    const uint8_t *bytes = (const uint8_t *)input;
+    char out[64];
+    char what[128];
+
+    for (uint32_t part = 1; part <= 2; part++) {
+        int code = aoc_solve(day, part, bytes, len, out, sizeof out);
+        snprintf(what, sizeof what, "day %u part %u is %s (got %s)", day, part, argv[2 + part], code == AOC_OK ? out : "nothing");
+        check(code == AOC_OK && strcmp(out, argv[2 + part]) == 0, what);
+    }
+
+    check(aoc_solve(0, 1, bytes, len, out, sizeof out) == AOC_ERR_ARGUMENT, "day 0 is an argument error");
+    check(aoc_solve(day, 3, bytes, len, out, sizeof out) == AOC_ERR_ARGUMENT, "part 3 is an argument error");
+    check(aoc_solve(day, 1, NULL, len, out, sizeof out) == AOC_ERR_ARGUMENT, "null input is an argument error");
+    check(aoc_solve(day, 1, bytes, len, NULL, 0) == AOC_ERR_ARGUMENT, "null out is an argument error");
+    check(aoc_solve(day, 1, bytes, len, out, 1) == AOC_ERR_BUFFER, "a one byte buffer is too small");
+    check(strlen(aoc_last_error()) > 0, "the buffer error is described");
+
+    const char *garbage = "not a puzzle input\n";
+    int code = aoc_solve(day, 1, (const uint8_t *)garbage, strlen(garbage), out, sizeof out);
+    check(code == AOC_ERR_SOLVER || code == AOC_ERR_NO_ANSWER, "garbage input fails without crashing the caller");
+
+    free(input);
+    printf("%d failed\n", failures);
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Expected answers from `answers.toml`, a small subset of TOML:
+///
+/// ```toml
+/// [day-1]
+/// part1 = 1451
+/// part2 = "1395"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(u8, u8), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("reading {}: {}", path.display(), e))?;
+        Answers::parse(&text).map_err(|e| format!("{}:{}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = Answers::default();
+        let mut day: Option<u8> = None;
+
+        for (idx, line) in text.lines().enumerate() {
+            let line_num = idx + 1;
+            let line = match line.find('#') {
+                Some(comment) if !line[..comment].contains('"') => &line[..comment],
+                _ => line,
+            }.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                day = Some(section.trim().strip_prefix("day-").and_then(|n| n.parse::<u8>().ok())
+                    .ok_or(format!("{}: expected a [day-N] section, found {}", line_num, line))?);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or(format!("{}: expected `partN = value`", line_num))?;
+            let part = key.trim().strip_prefix("part").and_then(|n| n.parse::<u8>().ok())
+                .ok_or(format!("{}: expected a partN key, found {}", line_num, key.trim()))?;
+            let day = day.ok_or(format!("{}: part{} outside of a [day-N] section", line_num, part))?;
+            let value = value.trim();
+            let value = match value.strip_prefix('"') {
+                Some(quoted) => quoted.strip_suffix('"').ok_or(format!("{}: unterminated string", line_num))?,
+                None => value,
+            };
+
+            if answers.expected.insert((day, part), value.to_string()).is_some() {
+                return Err(format!("{}: day-{} part{} given twice", line_num, day, part));
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn expected(&self, day: u8, part: u8) -> Option<&str> {
+        self.expected.get(&(day, part)).map(|value| value.as_str())
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::cache::Cache;
+use crate::days::Day;
+use crate::run::{self, Limits, Outcome, Status};
+
+/// Runs taking more than this many times the median are flagged as slow
+const SLOW_FACTOR: f64 = 3.0;
+
+/// Replaces directories with the files inside them, sorted by name
+pub fn expand(inputs: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut files: Vec<PathBuf> = vec!();
+    for input in inputs {
+        if input.is_dir() {
+            let entries = fs::read_dir(input).map_err(|e| format!("reading {}: {}", input.display(), e))?;
+            let mut inside: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).filter(|path| path.is_file()).collect();
+            inside.sort();
+            files.extend(inside);
+        } else {
+            files.push(input.clone());
+        }
+    }
+    match files.is_empty() {
+        true => Err("no input files".to_string()),
+        false => Ok(files),
+    }
+}
+
+/// `aoc batch`, runs a day over each input file and tabulates the results,
+/// returning false if any run failed
+pub fn batch(day: &Day, inputs: &[PathBuf], limits: Limits, cache: Option<&Cache>) -> bool {
+    let names: Vec<String> = inputs.iter().map(|input| input.display().to_string()).collect();
+    let name_width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0).max(4) + 2;
+
+    println!("{:<name_width$}{:<20}{:<20}{:<14}Status", "File", "Part 1", "Part 2", "Time", name_width = name_width);
+    println!("{}", "-".repeat(name_width + 60));
+
+    // print each run as it finishes, flagging slow ones once we know the median
+    let mut outcomes: Vec<Outcome> = vec!();
+    for (input, name) in inputs.iter().zip(&names) {
+        let outcome = run::run_day_cached(day, input, limits, cache);
+        print_row(name, name_width, &outcome, "");
+        outcomes.push(outcome);
+    }
+
+    let median = median(outcomes.iter().filter(|outcome| outcome.status == Status::Ok).map(|outcome| outcome.elapsed).collect());
+    let slow: Vec<(&String, &Outcome)> = names.iter().zip(&outcomes)
+        .filter(|(_, outcome)| outcome.status == Status::Ok && is_slow(outcome.elapsed, median))
+        .collect();
+    let failed: Vec<(&String, &Outcome)> = names.iter().zip(&outcomes).filter(|(_, outcome)| outcome.status != Status::Ok).collect();
+
+    if !slow.is_empty() || !failed.is_empty() {
+        println!("\r\nNeeds a look");
+        println!("{}", "-".repeat(name_width + 60));
+        for (name, outcome) in failed.iter().chain(&slow) {
+            let note = match median {
+                Some(median) if outcome.status == Status::Ok => format!("SLOW {:.1}x median", ratio(outcome.elapsed, median)),
+                _ => String::new(),
+            };
+            print_row(name, name_width, outcome, &note);
+        }
+    }
+
+    let median = median.map(|median| format!("{:.1?}", median)).unwrap_or_else(|| "-".to_string());
+    println!("\r\n{} files, {} failed, {} slow, median time {}", outcomes.len(), failed.len(), slow.len(), median);
+    failed.is_empty()
+}
+
+fn print_row(name: &str, name_width: usize, outcome: &Outcome, note: &str) {
+    let status = match &outcome.status {
+        Status::Ok if note.is_empty() && outcome.cached => "ok (cached)".to_string(),
+        Status::Ok if note.is_empty() => "ok".to_string(),
+        Status::Ok => note.to_string(),
+        status => format!("{} {}", status.label().to_uppercase(), status.detail()),
+    };
+    println!(
+        "{:<name_width$}{:<20}{:<20}{:<14}{}",
+        name, outcome.answer(1).unwrap_or("-"), outcome.answer(2).unwrap_or("-"), format!("{:.1?}", outcome.elapsed), status,
+        name_width = name_width
+    );
+}
+
+fn median(mut timings: Vec<Duration>) -> Option<Duration> {
+    if timings.is_empty() {
+        return None;
+    }
+    timings.sort_unstable();
+    let mid = timings.len() / 2;
+    match timings.len() % 2 {
+        0 => Some((timings[mid - 1] + timings[mid]) / 2),
+        _ => Some(timings[mid]),
+    }
+}
+
+fn ratio(elapsed: Duration, median: Duration) -> f64 {
+    elapsed.as_secs_f64() / median.as_secs_f64().max(f64::EPSILON)
+}
+
+fn is_slow(elapsed: Duration, median: Option<Duration>) -> bool {
+    median.is_some_and(|median| ratio(elapsed, median) > SLOW_FACTOR)
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc::hash::Fnv1a;
+
+use crate::days::{self, Day};
+use crate::run::{Outcome, Status};
+
+/// Answers from earlier runs, one file per day, input and source revision,
+/// so unchanged heavy days come back instantly
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf) -> Self {
+        Cache { dir }
+    }
+
+    /// Hash of the day, its input bytes and the sources it is built from,
+    /// which changes whenever any of them do
+    pub fn key(&self, day: &Day, input: &Path) -> io::Result<u64> {
+        let mut hasher = Fnv1a::default();
+        hasher.write_u8(day.number);
+        hash_bytes(&mut hasher, &fs::read(input)?);
+        hash_sources(&mut hasher, &day.dir)?;
+        hash_sources(&mut hasher, &days::root().join("aoc"))?;
+        Ok(hasher.finish())
+    }
+
+    fn path(&self, day: &Day, key: u64) -> PathBuf {
+        self.dir.join(day.name()).join(format!("{:016x}", key))
+    }
+
+    /// The cached outcome of a successful run, if there is one
+    pub fn get(&self, day: &Day, key: u64) -> Option<Outcome> {
+        let text = fs::read_to_string(self.path(day, key)).ok()?;
+        let mut outcome = Outcome { status: Status::Ok, answers: vec!(), elapsed: Duration::ZERO, cached: true };
+        for line in text.lines() {
+            let (name, value) = line.split_once(" = ")?;
+            if name == "elapsed" {
+                outcome.elapsed = Duration::from_nanos(value.parse().ok()?);
+            } else if let Some(part) = name.strip_prefix("part").and_then(|part| part.parse::<usize>().ok()) {
+                if outcome.answers.len() < part {
+                    outcome.answers.resize(part, None);
+                }
+                outcome.answers[part - 1] = Some(value.to_string());
+            }
+        }
+        Some(outcome)
+    }
+
+    /// Remembers a successful run, failures are always rerun
+    pub fn put(&self, day: &Day, key: u64, outcome: &Outcome) -> io::Result<()> {
+        if outcome.status != Status::Ok {
+            return Ok(());
+        }
+        let mut text = format!("elapsed = {}\n", outcome.elapsed.as_nanos());
+        for (idx, answer) in outcome.answers.iter().enumerate() {
+            if let Some(answer) = answer {
+                text.push_str(&format!("part{} = {}\n", idx + 1, answer));
+            }
+        }
+        let path = self.path(day, key);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, text)
+    }
+}
+
+/// Hashes Cargo.toml and everything under src/, skipping the runner's own
+/// sources since they don't change any answers
+fn hash_sources(hasher: &mut Fnv1a, crate_dir: &Path) -> io::Result<()> {
+    let mut files = vec![crate_dir.join("Cargo.toml")];
+    collect_files(&crate_dir.join("src"), &mut files)?;
+    files.sort();
+    for file in files.iter().filter(|file| !file.starts_with(crate_dir.join("src").join("bin"))) {
+        hash_bytes(hasher, file.strip_prefix(crate_dir).unwrap_or(file).to_string_lossy().as_bytes());
+        hash_bytes(hasher, &fs::read(file)?);
+    }
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Length prefixed so neighbouring files can't run into each other
+fn hash_bytes(hasher: &mut Fnv1a, bytes: &[u8]) {
+    hasher.write_u64(bytes.len() as u64);
+    hasher.write(bytes);
+}
@@ -0,0 +1,75 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A `day-N` crate within the workspace
+#[derive(Debug, Clone)]
+pub struct Day {
+    pub number: u8,
+    pub dir: PathBuf,
+}
+
+impl Day {
+    /// Crate, directory and binary name
+    pub fn name(&self) -> String {
+        format!("day-{}", self.number)
+    }
+
+    /// The puzzle input checked in alongside the day
+    pub fn input(&self) -> PathBuf {
+        self.dir.join("input.txt")
+    }
+
+    /// Built binary, living next to our own executable in the target dir
+    pub fn binary(&self) -> PathBuf {
+        let exe = env::current_exe().expect("no current executable");
+        exe.with_file_name(format!("{}{}", self.name(), env::consts::EXE_SUFFIX))
+    }
+}
+
+/// Workspace root, one level above this crate
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+/// Finds all `day-N` directories under root, keeping only `selected` when given
+pub fn discover(root: &Path, selected: &[u8]) -> Result<Vec<Day>, String> {
+    let entries = fs::read_dir(root).map_err(|e| format!("reading {}: {}", root.display(), e))?;
+    let mut days: Vec<Day> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let number = name.strip_prefix("day-")?.parse::<u8>().ok()?;
+            Some(Day { number, dir: entry.path() })
+        })
+        .filter(|day| selected.is_empty() || selected.contains(&day.number))
+        .collect();
+    days.sort_by_key(|day| day.number);
+
+    if let Some(missing) = selected.iter().find(|&&n| !days.iter().any(|day| day.number == n)) {
+        return Err(format!("no day-{} directory in {}", missing, root.display()));
+    }
+    Ok(days)
+}
+
+/// Builds the day binaries with the same profile we were built with
+pub fn build(days: &[Day]) -> Result<(), String> {
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    let profile = exe.parent().and_then(|dir| dir.file_name()).and_then(|name| name.to_str()).unwrap_or("debug");
+
+    let mut cargo = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cargo.arg("build").arg("--quiet").arg("--manifest-path").arg(root().join("Cargo.toml"));
+    if profile != "debug" {
+        cargo.arg("--profile").arg(profile);
+    }
+    for day in days {
+        cargo.arg("-p").arg(day.name());
+    }
+
+    let status = cargo.status().map_err(|e| format!("running cargo: {}", e))?;
+    if !status.success() {
+        return Err(format!("cargo build failed ({})", status));
+    }
+    Ok(())
+}
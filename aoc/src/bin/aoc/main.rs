@@ -0,0 +1,183 @@
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+use structopt::StructOpt;
+
+mod answers;
+mod batch;
+mod cache;
+mod days;
+mod run;
+mod scale;
+mod tui;
+mod verify;
+
+use answers::Answers;
+use cache::Cache;
+use days::Day;
+use run::Limits;
+
+/// Advent of Code 2021 runner
+#[derive(StructOpt)]
+#[structopt(name = "aoc")]
+enum Cli {
+    /// Runs days on their input.txt, printing answers and timings
+    Run(Selection),
+    /// Runs days on their input.txt and checks answers against answers.toml
+    Verify(Selection),
+    /// Full screen launcher for browsing and running days
+    Tui(Selection),
+    /// Times a day's registered implementations of each part side by side
+    Bench {
+        day: u8,
+        /// Runs of each implementation
+        #[structopt(short = "n", long, default_value = "100")]
+        runs: u32,
+        /// Use the already built day binary instead of building it first
+        #[structopt(long)]
+        no_build: bool,
+    },
+    /// Runs a day over several input files, comparing answers and timings
+    Batch {
+        day: u8,
+        /// Input files, or directories of them
+        #[structopt(required = true, parse(from_os_str))]
+        inputs: Vec<PathBuf>,
+        /// Use the already built day binary instead of building it first
+        #[structopt(long)]
+        no_build: bool,
+        /// Rerun every input instead of answering from the cache
+        #[structopt(long)]
+        no_cache: bool,
+        #[structopt(flatten)]
+        limits: LimitOptions,
+    },
+    /// Times a day over generated inputs of growing size and fits how it scales
+    Scale {
+        day: u8,
+        /// First size, in the day's own unit, instead of its default
+        #[structopt(long)]
+        start: Option<usize>,
+        /// Sizes to try, each larger than the last
+        #[structopt(long, default_value = "8")]
+        steps: usize,
+        /// Runs at each size, keeping the fastest
+        #[structopt(short = "n", long, default_value = "3")]
+        runs: u32,
+        /// Stop growing once a run takes longer than this many seconds
+        #[structopt(long, default_value = "10", parse(try_from_str = seconds))]
+        budget: Duration,
+        /// Use the already built day binary instead of building it first
+        #[structopt(long)]
+        no_build: bool,
+        #[structopt(flatten)]
+        limits: LimitOptions,
+    },
+}
+
+/// Which days to run, shared by all commands
+#[derive(StructOpt)]
+struct Selection {
+    /// Day numbers, every day when omitted
+    days: Vec<u8>,
+    /// Use the already built day binaries instead of building them first
+    #[structopt(long)]
+    no_build: bool,
+    /// Rerun every day instead of answering from the cache
+    #[structopt(long)]
+    no_cache: bool,
+    #[structopt(flatten)]
+    limits: LimitOptions,
+}
+
+/// Resource limits for each day's process
+#[derive(StructOpt)]
+struct LimitOptions {
+    /// Seconds each day may run before it is killed, 0 for no limit
+    #[structopt(long, default_value = "60", parse(try_from_str = seconds))]
+    timeout: Duration,
+    /// Megabytes of address space each day may use, 0 for no limit
+    #[structopt(long = "memory", value_name = "memory", default_value = "4096", parse(try_from_str = megabytes))]
+    memory_bytes: u64,
+}
+
+impl LimitOptions {
+    fn limits(&self) -> Limits {
+        Limits {
+            timeout: Some(self.timeout).filter(|timeout| !timeout.is_zero()),
+            memory: Some(self.memory_bytes).filter(|bytes| *bytes > 0),
+        }
+    }
+}
+
+/// A non-negative number of seconds
+fn seconds(text: &str) -> Result<Duration, String> {
+    let seconds: f64 = text.parse().map_err(|_| format!("{:?} isn't a number of seconds", text))?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("{} isn't a usable number of seconds", text))
+}
+
+/// A number of megabytes, in bytes
+fn megabytes(text: &str) -> Result<u64, String> {
+    let megabytes: u64 = text.parse().map_err(|_| format!("{:?} isn't a number of megabytes", text))?;
+    megabytes.checked_mul(1024 * 1024).ok_or_else(|| format!("{} megabytes is too large", megabytes))
+}
+
+impl Selection {
+    /// Discovers and builds the selected days
+    fn prepare(&self) -> Result<Vec<Day>, String> {
+        let days = days::discover(&days::root(), &self.days)?;
+        if !self.no_build {
+            days::build(&days)?;
+        }
+        Ok(days)
+    }
+
+    /// Answers kept from earlier runs, unless `--no-cache` was given
+    fn cache(&self) -> Option<Cache> {
+        match self.no_cache {
+            true => None,
+            false => Some(Cache::new(days::root().join(".aoc-cache"))),
+        }
+    }
+}
+
+fn main() {
+    let result = match Cli::from_args() {
+        Cli::Run(selection) => selection.prepare().map(|days| {
+            run::print_days(&days, selection.limits.limits(), selection.cache().as_ref());
+            true
+        }),
+        Cli::Verify(selection) => selection.prepare().and_then(|days| {
+            let answers = Answers::load(&days::root().join("answers.toml"))?;
+            Ok(verify::verify(&days, &answers, selection.limits.limits(), selection.cache().as_ref()))
+        }),
+        Cli::Tui(selection) => selection.prepare().and_then(|days| {
+            let answers = Answers::load(&days::root().join("answers.toml"))?;
+            tui::tui(days, answers, selection.limits.limits()).map_err(|e| e.to_string())?;
+            Ok(true)
+        }),
+        Cli::Bench { day, runs, no_build } => Selection { days: vec![day], no_build, no_cache: true, limits: LimitOptions { timeout: Duration::ZERO, memory_bytes: 0 } }
+            .prepare()
+            .and_then(|days| run::bench_day(&days[0], runs)),
+        Cli::Batch { day, inputs, no_build, no_cache, limits } => {
+            let selection = Selection { days: vec![day], no_build, no_cache, limits };
+            selection.prepare().and_then(|days| {
+                let inputs = batch::expand(&inputs)?;
+                Ok(batch::batch(&days[0], &inputs, selection.limits.limits(), selection.cache().as_ref()))
+            })
+        }
+        Cli::Scale { day, start, steps, runs, budget, no_build, limits } => {
+            let selection = Selection { days: vec![day], no_build, no_cache: true, limits };
+            selection.prepare().and_then(|days| scale::scale(&days[0], start, steps, runs, budget, selection.limits.limits()))
+        }
+    };
+
+    match result {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }
+    }
+}
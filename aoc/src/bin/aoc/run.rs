@@ -0,0 +1,256 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::scrape::{panic_message, parse_answers};
+
+use crate::cache::Cache;
+use crate::days::Day;
+
+/// How a day's process finished
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Panicked(String),
+    Failed(String),
+    /// Killed once it ran past the wall-clock limit
+    TimedOut(Duration),
+    /// An allocation failed under the memory limit
+    OutOfMemory(String),
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Panicked(_) => "panicked",
+            Status::Failed(_) => "failed",
+            Status::TimedOut(_) => "timed out",
+            Status::OutOfMemory(_) => "out of memory",
+        }
+    }
+
+    pub fn detail(&self) -> String {
+        match self {
+            Status::Ok => String::new(),
+            Status::Panicked(msg) | Status::Failed(msg) | Status::OutOfMemory(msg) => msg.clone(),
+            Status::TimedOut(limit) => format!("after {:?}", limit),
+        }
+    }
+}
+
+/// Resource limits applied to each day's process
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    /// Wall-clock time before the day is killed
+    pub timeout: Option<Duration>,
+    /// Address space cap in bytes, enforced with `setrlimit`
+    pub memory: Option<u64>,
+}
+
+/// Result of running a single day over an input
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub status: Status,
+    /// Scraped answers, index 0 is part 1
+    pub answers: Vec<Option<String>>,
+    pub elapsed: Duration,
+    /// Answered from the cache, `elapsed` is from the run that filled it
+    pub cached: bool,
+}
+
+impl Outcome {
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.answers.get(part as usize - 1).and_then(|answer| answer.as_deref())
+    }
+}
+
+/// Runs the day binary with `input` redirected to stdin
+pub fn run_day(day: &Day, input: &Path, limits: Limits) -> Outcome {
+    run_day_streaming(day, input, limits, |_line| ())
+}
+
+/// Like [`run_day`], answering from `cache` when this input and source ran before
+pub fn run_day_cached(day: &Day, input: &Path, limits: Limits, cache: Option<&Cache>) -> Outcome {
+    let cache = cache.and_then(|cache| cache.key(day, input).ok().map(|key| (cache, key)));
+    if let Some(outcome) = cache.and_then(|(cache, key)| cache.get(day, key)) {
+        return outcome;
+    }
+    let outcome = run_day(day, input, limits);
+    if let Some((cache, key)) = cache {
+        if let Err(e) = cache.put(day, key, &outcome) {
+            eprintln!("warning: unable to cache day {}: {}", day.number, e);
+        }
+    }
+    outcome
+}
+
+/// Runs the day binary, handing each line of stdout to `on_line` as it is printed
+pub fn run_day_streaming(day: &Day, input: &Path, limits: Limits, mut on_line: impl FnMut(&str)) -> Outcome {
+    let failed = |msg: String| Outcome { status: Status::Failed(msg), answers: vec!(), elapsed: Duration::ZERO, cached: false };
+
+    let stdin = match File::open(input) {
+        Ok(file) => file,
+        Err(e) => return failed(format!("opening {}: {}", input.display(), e)),
+    };
+
+    let mut command = Command::new(day.binary());
+    command.stdin(stdin).stdout(Stdio::piped()).stderr(Stdio::piped());
+    if let Some(bytes) = limits.memory {
+        limit_memory(&mut command, bytes);
+    }
+
+    let start = Instant::now();
+    let deadline = limits.timeout.map(|timeout| start + timeout);
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => return failed(format!("starting {}: {}", day.binary().display(), e)),
+    };
+
+    // drain stderr on the side so a chatty day can't block on a full pipe
+    let mut stderr_pipe = child.stderr.take().unwrap();
+    let stderr_reader = thread::spawn(move || {
+        let mut stderr = String::new();
+        let _ = stderr_pipe.read_to_string(&mut stderr);
+        stderr
+    });
+
+    // and stdout too, so we can stop waiting on it at the deadline
+    let (sender, receiver) = mpsc::channel();
+    let stdout_pipe = child.stdout.take().unwrap();
+    let stdout_reader = thread::spawn(move || {
+        let mut reader = BufReader::new(stdout_pipe);
+        let mut line: Vec<u8> = vec!();
+        while matches!(reader.read_until(b'\n', &mut line), Ok(len) if len > 0) {
+            if sender.send(String::from_utf8_lossy(&line).into_owned()).is_err() {
+                break;
+            }
+            line.clear();
+        }
+    });
+
+    let mut stdout = String::new();
+    let mut timed_out = false;
+    loop {
+        let line = match deadline {
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            // checked up front as well, a day printing nonstop never lets recv time out
+            Some(deadline) if Instant::now() >= deadline => Err(RecvTimeoutError::Timeout),
+            Some(deadline) => receiver.recv_timeout(deadline - Instant::now()),
+        };
+        match line {
+            Ok(text) => {
+                on_line(text.trim_end());
+                stdout.push_str(&text);
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                timed_out = true;
+                break;
+            }
+        }
+    }
+
+    let exit = if timed_out { Ok(None) } else { wait_until(&mut child, deadline) };
+    if let Ok(None) = exit {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+    let elapsed = start.elapsed();
+    drop(receiver);
+    let _ = stdout_reader.join();
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    let status = match exit {
+        Ok(Some(exit)) if exit.success() => Status::Ok,
+        Ok(Some(exit)) => failure_status(&stderr).unwrap_or_else(|| Status::Failed(exit.to_string())),
+        Ok(None) => Status::TimedOut(limits.timeout.unwrap_or_default()),
+        Err(e) => Status::Failed(e.to_string()),
+    };
+
+    Outcome { status, answers: parse_answers(&stdout), elapsed, cached: false }
+}
+
+/// Waits for the child to exit, giving up with `None` at the deadline
+fn wait_until(child: &mut Child, deadline: Option<Instant>) -> io::Result<Option<ExitStatus>> {
+    let deadline = match deadline {
+        None => return child.wait().map(Some),
+        Some(deadline) => deadline,
+    };
+    // polled quickly at first, as stdout has usually just closed with the
+    // day about to exit, so short runs aren't rounded up to the interval
+    let mut interval = Duration::from_micros(100);
+    loop {
+        if let Some(exit) = child.try_wait()? {
+            return Ok(Some(exit));
+        }
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        thread::sleep(interval);
+        interval = (interval * 2).min(Duration::from_millis(10));
+    }
+}
+
+/// Caps the child's address space so runaway allocations fail instead of
+/// taking the machine down with them
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit { rlim_cur: bytes as libc::rlim_t, rlim_max: bytes as libc::rlim_t };
+    // only async-signal-safe calls between fork and exec, setrlimit is one
+    unsafe {
+        command.pre_exec(move || match libc::setrlimit(libc::RLIMIT_AS, &limit) {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_command: &mut Command, _bytes: u64) {}
+
+/// Works out why a day exited unsuccessfully from what it left on stderr
+fn failure_status(stderr: &str) -> Option<Status> {
+    if let Some(line) = stderr.lines().find(|line| line.starts_with("memory allocation of")) {
+        return Some(Status::OutOfMemory(line.to_string()));
+    }
+    if let Some(line) = stderr.lines().find(|line| line.contains("has overflowed its stack")) {
+        return Some(Status::Failed(line.to_string()));
+    }
+    panic_message(stderr).map(Status::Panicked)
+}
+
+/// `aoc bench`, runs a day with `AOC_BENCH` set so its registered
+/// implementations are repeated and timed, panicking if they disagree
+pub fn bench_day(day: &Day, runs: u32) -> Result<bool, String> {
+    let input = File::open(day.input()).map_err(|e| format!("opening {}: {}", day.input().display(), e))?;
+    let status = Command::new(day.binary())
+        .env("AOC_BENCH", runs.to_string())
+        .stdin(input)
+        .status()
+        .map_err(|e| format!("starting {}: {}", day.binary().display(), e))?;
+    Ok(status.success())
+}
+
+/// `aoc run`, prints each day's answers as they finish
+pub fn print_days(days: &[Day], limits: Limits, cache: Option<&Cache>) {
+    let mut total = Duration::ZERO;
+    for day in days {
+        let outcome = run_day_cached(day, &day.input(), limits, cache);
+        total += outcome.elapsed;
+        println!("Day {}\t({:?}{})", day.number, outcome.elapsed, if outcome.cached { ", cached" } else { "" });
+        for part in 1..=2 {
+            println!("  Part {}: {}", part, outcome.answer(part).unwrap_or("-"));
+        }
+        if outcome.status != Status::Ok {
+            println!("  {}: {}", outcome.status.label(), outcome.status.detail());
+        }
+    }
+    println!("Total execution time: {:?}", total);
+}
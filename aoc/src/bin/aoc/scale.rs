@@ -0,0 +1,475 @@
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Duration;
+
+use crate::days::Day;
+use crate::run::{self, Limits, Outcome, Status};
+
+/// How input sizes grow from one step to the next
+#[derive(Clone, Copy)]
+enum Growth {
+    Times(usize),
+    Plus(usize),
+}
+
+/// Makes an input of a given size
+type Generate = fn(usize, &mut Rng) -> String;
+
+/// Makes inputs of a given size for one day, `size` counted in `unit`s
+struct Generator {
+    unit: &'static str,
+    start: usize,
+    growth: Growth,
+    generate: Generate,
+}
+
+/// `aoc scale`, times a day over synthesised inputs of growing size and
+/// fits how its run time grows, returning false if a run failed
+pub fn scale(day: &Day, start: Option<usize>, steps: usize, runs: u32, budget: Duration, limits: Limits) -> Result<bool, String> {
+    let generator = generator(day.number).ok_or_else(|| format!("{} has no input generator, its input doesn't grow", day.name()))?;
+    let path = env::temp_dir().join(format!("aoc-scale-{}-{}.txt", day.name(), process::id()));
+    let mut rng = Rng::new(u64::from(day.number));
+
+    println!("{:<14}{:<14}{:<12}{:<12}{:<10}Status", format!("Size ({})", generator.unit), "Time", "log10 n", "log10 t", "Local k");
+    println!("{}", "-".repeat(70));
+
+    let mut points: Vec<(f64, f64)> = vec!();
+    let mut size = start.unwrap_or(generator.start).max(1);
+    let mut ok = true;
+    for _ in 0..steps {
+        let input = (generator.generate)(size, &mut rng);
+        fs::write(&path, input).map_err(|e| format!("writing {}: {}", path.display(), e))?;
+
+        // the fastest of a few runs, the least disturbed by anything else going on
+        let mut best: Option<Outcome> = None;
+        for _ in 0..runs.max(1) {
+            let outcome = run::run_day(day, &path, limits);
+            let failed = outcome.status != Status::Ok;
+            if best.as_ref().is_none_or(|best| failed || outcome.elapsed < best.elapsed) {
+                best = Some(outcome);
+            }
+            if failed {
+                break;
+            }
+        }
+        let outcome = best.unwrap();
+
+        if outcome.status != Status::Ok {
+            println!("{:<14}{:<14}{:<12}{:<12}{:<10}{} {}", size, "-", "-", "-", "-", outcome.status.label().to_uppercase(), outcome.status.detail());
+            ok = false;
+            break;
+        }
+
+        let (n, t) = (size as f64, outcome.elapsed.as_secs_f64());
+        let local = match points.last() {
+            Some(&(prev_n, prev_t)) => format!("{:.2}", (t / prev_t).ln() / (n / prev_n).ln()),
+            None => "-".to_string(),
+        };
+        println!("{:<14}{:<14}{:<12.3}{:<12.3}{:<10}ok", size, format!("{:.1?}", outcome.elapsed), n.log10(), t.log10(), local);
+        points.push((n, t));
+
+        if outcome.elapsed > budget {
+            println!("(stopping, a run took longer than {:?})", budget);
+            break;
+        }
+        size = match generator.growth {
+            Growth::Times(factor) => size * factor,
+            Growth::Plus(step) => size + step,
+        };
+    }
+    let _ = fs::remove_file(&path);
+
+    println!();
+    println!("{}", summary(&points));
+    Ok(ok)
+}
+
+/// Fits the larger half of the sizes, where process start up no longer
+/// dominates, both as a power of n and as an exponential in n
+fn summary(points: &[(f64, f64)]) -> String {
+    if points.len() < 3 {
+        return "Not enough sizes to fit a growth rate".to_string();
+    }
+    let upper = &points[points.len() - (points.len() / 2).max(3)..];
+    let power = fit(upper.iter().map(|&(n, t)| (n.ln(), t.ln())));
+    let exponential = fit(upper.iter().map(|&(n, t)| (n, t.ln())));
+    let from = format!("sizes {} to {}", upper[0].0, upper[upper.len() - 1].0);
+
+    // over a narrow range of sizes, as when they grow a unit at a time, both
+    // fits look good by r², but an exponential leaves far smaller residuals
+    if exponential.residual < power.residual / 2.0 && power.slope > 1.5 {
+        return format!("Growth looks exponential, about x{:.2} per unit of size over {} (r² {:.3})", exponential.slope.exp(), from, exponential.r2);
+    }
+    let k = power.slope;
+    let shape = match k {
+        k if k < 0.5 => "roughly constant, fixed costs dominate",
+        k if k < 1.3 => "about linear",
+        k if k < 1.7 => "between linear and quadratic",
+        k if k < 2.4 => "about quadratic",
+        k if k < 3.4 => "about cubic",
+        _ => "worse than cubic",
+    };
+    format!("Growth ~ n^{:.2}, {}, over {} (r² {:.3})", k, shape, from, power.r2)
+}
+
+/// A least squares line
+struct Fit {
+    slope: f64,
+    /// How much of the variation the line explains
+    r2: f64,
+    /// Sum of the squared distances of the points from the line
+    residual: f64,
+}
+
+fn fit(points: impl Iterator<Item = (f64, f64)> + Clone) -> Fit {
+    let len = points.clone().count() as f64;
+    let (mean_x, mean_y) = points.clone().fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x / len, sy + y / len));
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+        syy += (y - mean_y) * (y - mean_y);
+    }
+    let slope = sxy / sxx;
+    let r2 = if syy == 0.0 { 1.0 } else { (sxy * sxy) / (sxx * syy) };
+    Fit { slope, r2, residual: (syy - slope * sxy).max(0.0) }
+}
+
+/// Small seeded xorshift generator, so the same sizes give the same inputs
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// In `0..n`
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// In `lo..=hi`
+    fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + (self.next() % (hi - lo + 1) as u64) as i64
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn generator(day: u8) -> Option<Generator> {
+    let (unit, start, growth, generate): (&str, usize, Growth, Generate) = match day {
+        1 => ("lines", 1000, Growth::Times(2), depths),
+        2 => ("lines", 1000, Growth::Times(2), commands),
+        3 => ("lines", 1000, Growth::Times(2), diagnostics),
+        4 => ("boards", 100, Growth::Times(2), bingo),
+        5 => ("lines", 500, Growth::Times(2), vents),
+        6 => ("fish", 1000, Growth::Times(2), lanternfish),
+        7 => ("crabs", 1000, Growth::Times(2), crabs),
+        8 => ("lines", 200, Growth::Times(2), displays),
+        9 => ("cells", 1024, Growth::Times(4), heightmap),
+        10 => ("lines", 100, Growth::Times(2), brackets),
+        12 => ("caves", 4, Growth::Plus(1), caves),
+        13 => ("points", 1000, Growth::Times(2), dots),
+        14 => ("chars", 1000, Growth::Times(2), polymer),
+        15 => ("cells", 256, Growth::Times(4), risks),
+        16 => ("literals", 64, Growth::Times(2), transmission),
+        17 => ("distance", 10, Growth::Times(2), target),
+        18 => ("numbers", 25, Growth::Times(2), snailfish),
+        19 => ("beacons", 40, Growth::Times(2), scanners),
+        20 => ("cells", 256, Growth::Times(4), image),
+        _ => return None,
+    };
+    Some(Generator { unit, start, growth, generate })
+}
+
+/// Side of a square grid with about `cells` cells
+fn side(cells: usize) -> usize {
+    ((cells as f64).sqrt().round() as usize).max(2)
+}
+
+fn grid(cells: usize, rng: &mut Rng, cell: impl Fn(&mut Rng) -> char) -> String {
+    let side = side(cells);
+    (0..side).map(|_| (0..side).map(|_| cell(rng)).collect::<String>() + "\n").collect()
+}
+
+fn depths(n: usize, rng: &mut Rng) -> String {
+    let mut depth: i64 = 100;
+    (0..n).map(|_| {
+        depth = (depth + rng.range(-10, 12)).max(0);
+        format!("{}\n", depth)
+    }).collect()
+}
+
+/// Aim wanders about zero, so the products stay in range
+fn commands(n: usize, rng: &mut Rng) -> String {
+    let mut aim: i64 = 0;
+    (0..n).map(|_| match rng.below(3) {
+        0 if aim < 3 => { aim += 1; "down 1\n".to_string() }
+        1 if aim > -3 => { aim -= 1; "up 1\n".to_string() }
+        _ => format!("forward {}\n", rng.range(1, 3)),
+    }).collect()
+}
+
+/// Distinct reports, so the rating filters always end on one
+fn diagnostics(n: usize, rng: &mut Rng) -> String {
+    let bits = (usize::BITS - n.leading_zeros() + 2).clamp(5, 32) as usize;
+    let mut seen: HashSet<u64> = HashSet::new();
+    let mut text = String::new();
+    while seen.len() < n {
+        let report = rng.next() & ((1u64 << bits) - 1);
+        if seen.insert(report) {
+            text.push_str(&format!("{:0width$b}\n", report, width = bits));
+        }
+    }
+    text
+}
+
+fn bingo(n: usize, rng: &mut Rng) -> String {
+    let mut numbers: Vec<usize> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let mut text = numbers.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(",") + "\n";
+    for _ in 0..n {
+        rng.shuffle(&mut numbers);
+        text.push('\n');
+        for row in numbers[..25].chunks(5) {
+            text.push_str(&row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<String>>().join(" "));
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Horizontal, vertical and diagonal lines across a 1000 square
+fn vents(n: usize, rng: &mut Rng) -> String {
+    (0..n).map(|_| {
+        let len = rng.range(1, 200);
+        let (dx, dy) = [(1, 0), (0, 1), (1, 1), (1, -1)][rng.below(4)];
+        let x1 = rng.range(0, 999 - dx * len);
+        let y1 = if dy < 0 { rng.range(len, 999) } else { rng.range(0, 999 - dy * len) };
+        format!("{},{} -> {},{}\n", x1, y1, x1 + dx * len, y1 + dy * len)
+    }).collect()
+}
+
+fn lanternfish(n: usize, rng: &mut Rng) -> String {
+    (0..n).map(|_| rng.range(1, 5).to_string()).collect::<Vec<String>>().join(",") + "\n"
+}
+
+fn crabs(n: usize, rng: &mut Rng) -> String {
+    (0..n).map(|_| rng.range(0, 100).to_string()).collect::<Vec<String>>().join(",") + "\n"
+}
+
+/// Scrambled seven segment displays, each with its own wiring
+fn displays(n: usize, rng: &mut Rng) -> String {
+    const DIGITS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
+    let mut text = String::new();
+    for _ in 0..n {
+        let mut wiring: Vec<char> = "abcdefg".chars().collect();
+        rng.shuffle(&mut wiring);
+        let encode = |digit: usize, rng: &mut Rng| {
+            let mut wires: Vec<char> = DIGITS[digit].chars().map(|c| wiring[(c as u8 - b'a') as usize]).collect();
+            rng.shuffle(&mut wires);
+            wires.into_iter().collect::<String>()
+        };
+        let mut order: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut order);
+        let patterns: Vec<String> = order.iter().map(|&digit| encode(digit, rng)).collect();
+        let output: Vec<String> = (0..4).map(|_| { let digit = rng.below(10); encode(digit, rng) }).collect();
+        text.push_str(&format!("{} | {}\n", patterns.join(" "), output.join(" ")));
+    }
+    text
+}
+
+fn heightmap(n: usize, rng: &mut Rng) -> String {
+    grid(n, rng, |rng| if rng.chance(0.2) { '9' } else { char::from(b'0' + rng.below(9) as u8) })
+}
+
+/// Mostly incomplete lines, some corrupted partway
+fn brackets(n: usize, rng: &mut Rng) -> String {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    let mut text = String::new();
+    for line in 0..n {
+        let mut open: Vec<usize> = vec!();
+        let corrupt = line > 0 && rng.chance(0.3);
+        let len = rng.range(20, 100) as usize;
+        for i in 0..len {
+            if corrupt && i == len / 2 && !open.is_empty() {
+                let expected = *open.last().unwrap();
+                text.push(PAIRS[(expected + 1 + rng.below(3)) % 4].1);
+            } else if open.is_empty() || rng.chance(0.55) {
+                let kind = rng.below(4);
+                open.push(kind);
+                text.push(PAIRS[kind].0);
+            } else {
+                text.push(PAIRS[open.pop().unwrap()].1);
+            }
+        }
+        // always left open, so there are incomplete lines to score
+        text.push(PAIRS[rng.below(4)].0);
+        text.push('\n');
+    }
+    text
+}
+
+/// A chain of small caves, each neighbouring pair also joined through a big
+/// cave, so the number of walks grows exponentially with the chain
+fn caves(n: usize, _rng: &mut Rng) -> String {
+    let name = |i: usize| format!("{}{}", char::from(b'a' + (i / 26) as u8), char::from(b'a' + (i % 26) as u8));
+    let big = |i: usize| name(i).to_uppercase();
+    let mut text = format!("start-{}\n", name(0));
+    for i in 0..n - 1 {
+        text.push_str(&format!("{}-{}\n{}-{}\n{}-{}\n", name(i), name(i + 1), name(i), big(i), big(i), name(i + 1)));
+    }
+    text.push_str(&format!("{}-end\n", name(n - 1)));
+    text
+}
+
+/// Points unfolded back out from a 40x6 code, so no point lies on a fold
+fn dots(n: usize, rng: &mut Rng) -> String {
+    const FOLDS: [(char, i64); 12] = [('x', 655), ('y', 447), ('x', 327), ('y', 223), ('x', 163), ('y', 111), ('x', 81), ('y', 55), ('x', 40), ('y', 27), ('y', 13), ('y', 6)];
+    let mut points: HashSet<(i64, i64)> = HashSet::new();
+    let mut text = String::new();
+    // unfolding gives about a million places, stay well short of filling them
+    while points.len() < n.min(500_000) {
+        let (mut x, mut y) = (rng.range(0, 39), rng.range(0, 5));
+        for &(axis, at) in FOLDS.iter().rev() {
+            if rng.chance(0.5) {
+                match axis {
+                    'x' => x = 2 * at - x,
+                    _ => y = 2 * at - y,
+                }
+            }
+        }
+        if points.insert((x, y)) {
+            text.push_str(&format!("{},{}\n", x, y));
+        }
+    }
+    text.push('\n');
+    for (axis, at) in FOLDS {
+        text.push_str(&format!("fold along {}={}\n", axis, at));
+    }
+    text
+}
+
+/// A long template with a rule for every pair of ten elements
+fn polymer(n: usize, rng: &mut Rng) -> String {
+    let elements: Vec<char> = "BCFHKNOPSV".chars().collect();
+    let mut text: String = (0..n).map(|_| elements[rng.below(elements.len())]).collect();
+    text.push_str("\n\n");
+    for a in elements.iter() {
+        for b in elements.iter() {
+            text.push_str(&format!("{}{} -> {}\n", a, b, elements[rng.below(elements.len())]));
+        }
+    }
+    text
+}
+
+fn risks(n: usize, rng: &mut Rng) -> String {
+    grid(n, rng, |rng| char::from(b'1' + rng.below(9) as u8))
+}
+
+/// Literals nested under sum, minimum and maximum operators, which can't
+/// overflow however many there are
+fn transmission(n: usize, rng: &mut Rng) -> String {
+    fn bits(value: u64, width: usize) -> String {
+        format!("{:0width$b}", value, width = width)
+    }
+
+    let mut packets: Vec<String> = (0..n).map(|_| {
+        // four groups of four bits, all but the last flagged to continue
+        let value = rng.next() & 0xFFFF;
+        let groups: Vec<String> = (0..4).rev().map(|i| bits(((value >> (i * 4)) & 0xF) | if i > 0 { 0x10 } else { 0 }, 5)).collect();
+        format!("{}100{}", bits(rng.next() & 7, 3), groups.concat())
+    }).collect();
+    while packets.len() > 1 {
+        let mut grouped: Vec<String> = vec!();
+        let mut rest = &packets[..];
+        while !rest.is_empty() {
+            let take = rng.range(2, 8).min(rest.len() as i64) as usize;
+            let kind = [0, 2, 3][rng.below(3)];
+            grouped.push(format!("{}{}1{}{}", bits(rng.next() & 7, 3), bits(kind, 3), bits(take as u64, 11), rest[..take].concat()));
+            rest = &rest[take..];
+        }
+        packets = grouped;
+    }
+
+    let mut stream = packets.pop().unwrap();
+    // whole bytes, as the transmission is read a pair of hex digits at a time
+    while !stream.len().is_multiple_of(8) {
+        stream.push('0');
+    }
+    let hex: String = stream.as_bytes().chunks(4).map(|nibble| {
+        let value = u32::from_str_radix(std::str::from_utf8(nibble).unwrap(), 2).unwrap();
+        std::char::from_digit(value, 16).unwrap().to_ascii_uppercase()
+    }).collect();
+    hex + "\n"
+}
+
+fn target(n: usize, _rng: &mut Rng) -> String {
+    format!("target area: x={}..{}, y=-{}..-{}\n", n, n + n / 2, n, n / 2)
+}
+
+/// Already reduced numbers, pairs nested at most four deep
+fn snailfish(n: usize, rng: &mut Rng) -> String {
+    fn pair(depth: usize, rng: &mut Rng) -> String {
+        format!("[{},{}]", element(depth + 1, rng), element(depth + 1, rng))
+    }
+    fn element(depth: usize, rng: &mut Rng) -> String {
+        match depth < 4 && rng.chance(0.6) {
+            true => pair(depth, rng),
+            false => rng.below(10).to_string(),
+        }
+    }
+    (0..n).map(|_| pair(0, rng) + "\n").collect()
+}
+
+/// Four scanners 300 apart along x, each seeing the beacons within 1000
+/// of it on every axis from its own random orientation, with plenty of
+/// overlap between every pair
+fn scanners(n: usize, rng: &mut Rng) -> String {
+    use aoc::geometry::{Rotation, Vec3};
+
+    let mut beacons: HashSet<Vec3<i32>> = HashSet::new();
+    while beacons.len() < n {
+        beacons.insert(Vec3::new(rng.range(-700, 1600) as i32, rng.range(-700, 700) as i32, rng.range(-700, 700) as i32));
+    }
+    let mut text = String::new();
+    for scanner in 0..4 {
+        let location = Vec3::new(300 * scanner, rng.range(-50, 50) as i32, rng.range(-50, 50) as i32);
+        let facing = Rotation::ALL[rng.below(24)].inverse();
+        text.push_str(&format!("--- scanner {} ---\n", scanner));
+        for beacon in beacons.iter() {
+            let seen = *beacon - location;
+            if seen.x.abs() <= 1000 && seen.y.abs() <= 1000 && seen.z.abs() <= 1000 {
+                let seen = facing * seen;
+                text.push_str(&format!("{},{},{}\n", seen.x, seen.y, seen.z));
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// A lit first entry and dark last one, so the infinite background blinks
+fn image(n: usize, rng: &mut Rng) -> String {
+    let mut algorithm: String = (0..512).map(|_| if rng.chance(0.5) { '#' } else { '.' }).collect();
+    algorithm.replace_range(0..1, "#");
+    algorithm.replace_range(511..512, ".");
+    algorithm + "\n\n" + &grid(n, rng, |rng| if rng.chance(0.5) { '#' } else { '.' })
+}
@@ -0,0 +1,288 @@
+use std::fs::File;
+use std::io::{self, Stdout, Write};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use aoc::scrape;
+use crossterm::{
+    cursor, execute, queue,
+    event::{self, Event, KeyCode, KeyModifiers},
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
+    terminal::{self, ClearType},
+    Result,
+};
+
+use crate::answers::Answers;
+use crate::days::Day;
+use crate::run::{self, Limits, Outcome, Status};
+use crate::verify::Check;
+
+/// Width of the day list when the output panel fits beside it
+const LIST_WIDTH: u16 = 48;
+/// Narrowest output panel worth drawing, below this only the list is shown
+const MIN_PANEL_WIDTH: u16 = 30;
+/// Smallest terminal we attempt to draw in at all
+const MIN_WIDTH: u16 = 20;
+const MIN_HEIGHT: u16 = 4;
+/// Output lines kept per day
+const MAX_OUTPUT: usize = 1000;
+
+/// Arguments that switch a day into its terminal visualization
+fn visualize_args(day: u8) -> Option<&'static [&'static str]> {
+    match day {
+        9 => Some(&["1", "0", "250"]), // visualize, delay, refresh
+        11 => Some(&["1", "5"]),       // visualize, delay
+        15 => Some(&["-v"]),           // draws its path
+        _ => None,
+    }
+}
+
+/// Sent from the threads running days back to the event loop
+enum Message {
+    Line(usize, String),
+    Finished(usize, Outcome),
+}
+
+/// A day in the list along with its last run
+struct Row {
+    day: Day,
+    running: bool,
+    outcome: Option<Outcome>,
+    output: Vec<String>,
+}
+
+impl Row {
+    fn status(&self, answers: &Answers) -> (&'static str, Color) {
+        if self.running {
+            return ("running", Color::Yellow);
+        }
+        let outcome = match &self.outcome {
+            None => return ("-", Color::Grey),
+            Some(outcome) => outcome,
+        };
+        match outcome.status {
+            Status::Panicked(_) => ("PANICKED", Color::Red),
+            Status::Failed(_) => ("FAILED", Color::Red),
+            Status::TimedOut(_) => ("TIMED OUT", Color::Red),
+            Status::OutOfMemory(_) => ("OOM", Color::Red),
+            Status::Ok => {
+                let checks: Vec<Check> = (1..=2)
+                    .map(|part| Check::new(answers.expected(self.day.number, part), outcome.answer(part)))
+                    .collect();
+                if checks.contains(&Check::Mismatch) {
+                    ("MISMATCH", Color::Red)
+                } else if checks.iter().all(|check| *check == Check::Ok) {
+                    ("ok", Color::Green)
+                } else {
+                    ("unchecked", Color::DarkYellow)
+                }
+            }
+        }
+    }
+
+    fn summary(&self) -> (String, String) {
+        match &self.outcome {
+            None => (String::new(), String::new()),
+            Some(outcome) => (
+                format!("{:.1?}", outcome.elapsed),
+                format!("{} / {}", outcome.answer(1).unwrap_or("-"), outcome.answer(2).unwrap_or("-")),
+            ),
+        }
+    }
+}
+
+struct Tui {
+    rows: Vec<Row>,
+    answers: Answers,
+    limits: Limits,
+    selected: usize,
+    notice: Option<String>,
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+}
+
+/// `aoc tui`, a full screen launcher for browsing and running days
+pub fn tui(days: Vec<Day>, answers: Answers, limits: Limits) -> Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let rows = days.into_iter().map(|day| Row { day, running: false, outcome: None, output: vec!() }).collect();
+    let mut tui = Tui { rows, answers, limits, selected: 0, notice: None, sender, receiver };
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = tui.event_loop(&mut stdout);
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+impl Tui {
+    fn event_loop(&mut self, stdout: &mut Stdout) -> Result<()> {
+        let mut dirty = true;
+        loop {
+            while let Ok(message) = self.receiver.try_recv() {
+                self.receive(message);
+                dirty = true;
+            }
+            if dirty {
+                self.draw(stdout)?;
+                dirty = false;
+            }
+            if !event::poll(Duration::from_millis(50))? {
+                continue;
+            }
+
+            match event::read()? {
+                Event::Key(key) => {
+                    self.notice = None;
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+                        KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+                        KeyCode::Down => self.selected = (self.selected + 1).min(self.rows.len().saturating_sub(1)),
+                        KeyCode::Home => self.selected = 0,
+                        KeyCode::End => self.selected = self.rows.len().saturating_sub(1),
+                        KeyCode::Enter => self.run_selected(),
+                        KeyCode::Char('v') => self.visualize_selected(stdout)?,
+                        _ => (),
+                    }
+                }
+                Event::Resize(..) => (),
+                Event::Mouse(..) => continue,
+            }
+            dirty = true;
+        }
+    }
+
+    fn receive(&mut self, message: Message) {
+        match message {
+            Message::Line(idx, line) => {
+                let output = &mut self.rows[idx].output;
+                if output.len() == MAX_OUTPUT {
+                    output.remove(0);
+                }
+                output.push(line);
+            }
+            Message::Finished(idx, outcome) => {
+                let row = &mut self.rows[idx];
+                if outcome.status != Status::Ok {
+                    row.output.push(format!("{}: {}", outcome.status.label(), outcome.status.detail()));
+                }
+                row.running = false;
+                row.outcome = Some(outcome);
+            }
+        }
+    }
+
+    /// Runs the selected day on a background thread, streaming its output back
+    fn run_selected(&mut self) {
+        let idx = self.selected;
+        let row = match self.rows.get_mut(idx) {
+            Some(row) if !row.running => row,
+            _ => return,
+        };
+        row.running = true;
+        row.output.clear();
+
+        let day = row.day.clone();
+        let sender = self.sender.clone();
+        let limits = self.limits;
+        thread::spawn(move || {
+            let outcome = run::run_day_streaming(&day, &day.input(), limits, |line| {
+                let _ = sender.send(Message::Line(idx, scrape::plain_text(line)));
+            });
+            let _ = sender.send(Message::Finished(idx, outcome));
+        });
+    }
+
+    /// Hands the terminal over to the selected day's visualization
+    fn visualize_selected(&mut self, stdout: &mut Stdout) -> Result<()> {
+        let day = match self.rows.get(self.selected) {
+            Some(row) => row.day.clone(),
+            None => return Ok(()),
+        };
+        let args = match visualize_args(day.number) {
+            Some(args) => args,
+            None => {
+                self.notice = Some(format!("Day {} has no visualization", day.number));
+                return Ok(());
+            }
+        };
+
+        execute!(stdout, terminal::LeaveAlternateScreen, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0), cursor::Show)?;
+        terminal::disable_raw_mode()?;
+
+        let status = File::open(day.input()).and_then(|input| Command::new(day.binary()).args(args).stdin(input).status());
+        if let Err(e) = status {
+            self.notice = Some(format!("Day {} visualization failed: {}", day.number, e));
+        }
+
+        print!("\r\nPress any key to return");
+        stdout.flush()?;
+        terminal::enable_raw_mode()?;
+        while !matches!(event::read()?, Event::Key(_)) {}
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)
+    }
+
+    fn draw(&self, stdout: &mut Stdout) -> Result<()> {
+        let (width, height) = terminal::size()?;
+        queue!(stdout, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+        if width < MIN_WIDTH || height < MIN_HEIGHT {
+            queue!(stdout, Print(fit("Terminal too small", width)))?;
+            return stdout.flush();
+        }
+
+        let show_panel = width >= LIST_WIDTH + 1 + MIN_PANEL_WIDTH;
+        let list_width = if show_panel { LIST_WIDTH } else { width };
+        let body_height = (height - 2) as usize;
+
+        // day list, scrolled to keep the selection visible
+        queue!(stdout, SetAttribute(Attribute::Bold), Print(fit(" Day Status    Time      Answers", list_width)), SetAttribute(Attribute::Reset))?;
+        let first = (self.selected + 1).saturating_sub(body_height);
+        for (idx, row) in self.rows.iter().enumerate().skip(first).take(body_height) {
+            let y = (1 + idx - first) as u16;
+            let (status, color) = row.status(&self.answers);
+            let (time, answers) = row.summary();
+            let line = format!(" {:<4}{:<10}{:<10}{}", row.day.number, status, time, answers);
+
+            if idx == self.selected {
+                queue!(stdout, SetAttribute(Attribute::Reverse))?;
+            }
+            queue!(stdout, cursor::MoveTo(0, y), Print(fit(&line, list_width)))?;
+            if list_width > 5 {
+                queue!(stdout, cursor::MoveTo(5, y), SetForegroundColor(color), Print(fit(status, list_width - 5)), ResetColor)?;
+            }
+            queue!(stdout, SetAttribute(Attribute::Reset))?;
+        }
+
+        // output panel for the selected day
+        if show_panel {
+            let x = LIST_WIDTH + 1;
+            let panel_width = width - x;
+            for y in 0..height - 1 {
+                queue!(stdout, cursor::MoveTo(LIST_WIDTH, y), Print("│"))?;
+            }
+            if let Some(row) = self.rows.get(self.selected) {
+                let title = format!(" Day {} output", row.day.number);
+                queue!(stdout, cursor::MoveTo(x, 0), SetAttribute(Attribute::Bold), Print(fit(&title, panel_width)), SetAttribute(Attribute::Reset))?;
+                let skip = row.output.len().saturating_sub(body_height);
+                for (i, line) in row.output.iter().skip(skip).enumerate() {
+                    queue!(stdout, cursor::MoveTo(x, 1 + i as u16), Print(fit(line, panel_width)))?;
+                }
+            }
+        }
+
+        let footer = self.notice.as_deref().unwrap_or(" ↑/↓ select  Enter run  v visualize  q quit");
+        queue!(stdout, cursor::MoveTo(0, height - 1), SetAttribute(Attribute::Dim), Print(fit(footer, width)), SetAttribute(Attribute::Reset))?;
+        stdout.flush()
+    }
+}
+
+/// Pads or truncates text to exactly `width` columns
+fn fit(text: &str, width: u16) -> String {
+    let width = width as usize;
+    let truncated: String = text.chars().take(width).collect();
+    format!("{:<width$}", truncated, width = width)
+}
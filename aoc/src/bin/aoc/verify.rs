@@ -0,0 +1,72 @@
+use crate::answers::Answers;
+use crate::cache::Cache;
+use crate::days::Day;
+use crate::run::{self, Limits, Status};
+
+/// Comparison of a part's answer against answers.toml
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Ok,
+    Mismatch,
+    /// Expected answer given, but the day printed none
+    MissingAnswer,
+    /// Day printed an answer, but none is expected
+    MissingExpected,
+    Missing,
+}
+
+impl Check {
+    pub fn new(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match (expected, actual) {
+            (Some(e), Some(a)) if e == a => Check::Ok,
+            (Some(_), Some(_)) => Check::Mismatch,
+            (Some(_), None) => Check::MissingAnswer,
+            (None, Some(_)) => Check::MissingExpected,
+            (None, None) => Check::Missing,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Check::Ok => "ok",
+            Check::Mismatch => "MISMATCH",
+            Check::MissingAnswer => "missing answer",
+            Check::MissingExpected => "missing expected",
+            Check::Missing => "missing",
+        }
+    }
+}
+
+/// `aoc verify`, runs each day and compares against the expected answers,
+/// returning false on any mismatch or panic
+pub fn verify(days: &[Day], answers: &Answers, limits: Limits, cache: Option<&Cache>) -> bool {
+    let (mut ok, mut mismatched, mut missing, mut panicked) = (0, 0, 0, 0);
+
+    println!("{:<5}{:<6}{:<20}{:<20}Status", "Day", "Part", "Expected", "Actual");
+    println!("{}", "-".repeat(60));
+    for day in days {
+        let outcome = run::run_day_cached(day, &day.input(), limits, cache);
+        if outcome.status != Status::Ok {
+            panicked += 1;
+            let status = outcome.status.label().to_uppercase();
+            println!("{:<5}{:<6}{:<20}{:<20}{} {}", day.number, "-", "", "", status, outcome.status.detail());
+            continue;
+        }
+
+        for part in 1..=2 {
+            let expected = answers.expected(day.number, part);
+            let actual = outcome.answer(part);
+            let check = Check::new(expected, actual);
+            match check {
+                Check::Ok => ok += 1,
+                Check::Mismatch => mismatched += 1,
+                _ => missing += 1,
+            }
+            let cached = if outcome.cached { " (cached)" } else { "" };
+            println!("{:<5}{:<6}{:<20}{:<20}{}{}", day.number, part, expected.unwrap_or("-"), actual.unwrap_or("-"), check.label(), cached);
+        }
+    }
+
+    println!("\r\n{} ok, {} mismatched, {} missing, {} panicked or failed", ok, mismatched, missing, panicked);
+    mismatched == 0 && panicked == 0
+}
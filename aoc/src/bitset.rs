@@ -0,0 +1,375 @@
+//! Sets of small integers packed into bits: [`BitSet`] for a fixed
+//! number of up to 128 bits held in one word, [`DynBitSet`] for any
+//! number of bits decided at runtime.
+//!
+//! ```ignore
+//! let mut called: BitSet<25> = BitSet::new();
+//! called.insert(7);
+//! let bingo = (0..5).any(|i| BitSet::row(i, 5).is_subset(&called) || BitSet::column(i, 5, 5).is_subset(&called));
+//! ```
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr, Sub};
+
+/// A set of integers below `N`, where `N` is at most 128
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BitSet<const N: usize> {
+    bits: u128,
+}
+
+impl<const N: usize> BitSet<N> {
+    /// Every bit below `N`, failing to compile for an `N` that won't fit
+    const MASK: u128 = {
+        assert!(N <= 128, "BitSet holds at most 128 bits");
+        if N == 128 { u128::MAX } else { (1 << N) - 1 }
+    };
+
+    pub fn new() -> Self {
+        Self::from_bits(0)
+    }
+
+    /// Every integer below `N`
+    pub fn full() -> Self {
+        Self::from_bits(u128::MAX)
+    }
+
+    /// From a word, bit `i` holding `i`, dropping any bits from `N` up
+    pub fn from_bits(bits: u128) -> Self {
+        BitSet { bits: bits & Self::MASK }
+    }
+
+    pub fn bits(&self) -> u128 {
+        self.bits
+    }
+
+    /// Row `row` of a grid `cols` wide, numbered row by row
+    pub fn row(row: usize, cols: usize) -> Self {
+        (row * cols..(row + 1) * cols).collect()
+    }
+
+    /// Column `col` of a `rows` x `cols` grid, numbered row by row
+    pub fn column(col: usize, rows: usize, cols: usize) -> Self {
+        (0..rows).map(|row| row * cols + col).collect()
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        assert!(i < N, "{} out of range for BitSet<{}>", i, N);
+        self.bits |= 1 << i;
+    }
+
+    pub fn remove(&mut self, i: usize) {
+        if i < N {
+            self.bits &= !(1 << i);
+        }
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        match value {
+            true => self.insert(i),
+            false => self.remove(i),
+        }
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < N && self.bits & (1 << i) != 0
+    }
+
+    /// Number of integers in the set, the popcount
+    pub fn count_ones(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Whether every integer here is also in `other`, how a mask is tested
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.bits & other.bits == self.bits
+    }
+
+    /// The integers in the set, smallest first
+    pub fn iter(&self) -> Ones<u128> {
+        Ones { word: self.bits, base: 0 }
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        iter.into_iter().for_each(|i| set.insert(i));
+        set
+    }
+}
+
+impl<const N: usize> fmt::Debug for BitSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Implements a word level operator for `BitSet`
+macro_rules! bitset_op {
+    ($op:ident, $method:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<const N: usize> $op for BitSet<N> {
+            type Output = Self;
+            fn $method(self, other: Self) -> Self {
+                let ($a, $b) = (self.bits, other.bits);
+                BitSet::from_bits($body)
+            }
+        }
+    };
+}
+
+bitset_op!(BitAnd, bitand, |a, b| a & b);
+bitset_op!(BitOr, bitor, |a, b| a | b);
+bitset_op!(BitXor, bitxor, |a, b| a ^ b);
+bitset_op!(Sub, sub, |a, b| a & !b);
+
+impl<const N: usize> Not for BitSet<N> {
+    type Output = Self;
+    fn not(self) -> Self {
+        BitSet::from_bits(!self.bits)
+    }
+}
+
+impl<const N: usize> Shl<usize> for BitSet<N> {
+    type Output = Self;
+    fn shl(self, shift: usize) -> Self {
+        BitSet::from_bits(self.bits.checked_shl(shift as u32).unwrap_or(0))
+    }
+}
+
+impl<const N: usize> Shr<usize> for BitSet<N> {
+    type Output = Self;
+    fn shr(self, shift: usize) -> Self {
+        BitSet::from_bits(self.bits.checked_shr(shift as u32).unwrap_or(0))
+    }
+}
+
+/// Indexes of the set bits in a run of words
+pub struct Ones<W> {
+    word: W,
+    base: usize,
+}
+
+impl Iterator for Ones<u128> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.word == 0 {
+            return None;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(self.base + bit)
+    }
+}
+
+/// A set of integers below a length chosen at runtime, 64 to a word
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct DynBitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl DynBitSet {
+    /// An empty set for integers below `len`
+    pub fn with_len(len: usize) -> Self {
+        DynBitSet { words: vec![0; len.div_ceil(64)], len }
+    }
+
+    /// Number of integers the set can hold, not how many it does
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        assert!(i < self.len, "{} out of range for a DynBitSet of {}", i, self.len);
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn remove(&mut self, i: usize) {
+        if i < self.len {
+            self.words[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        match value {
+            true => self.insert(i),
+            false => self.remove(i),
+        }
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.len && self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Number of integers in the set, the popcount
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words.iter().zip(other.words.iter().chain(std::iter::repeat(&0))).all(|(a, b)| a & b == *a)
+    }
+
+    /// The integers in the set, smallest first
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, word)| Ones { word: *word, base: i * 64 })
+    }
+
+    /// Keeps only what is also in `other`, a word at a time
+    pub fn and_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a & b);
+    }
+
+    /// Adds everything in `other`, a word at a time
+    pub fn or_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a | b);
+    }
+
+    /// Flips everything in `other`, a word at a time
+    pub fn xor_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a ^ b);
+    }
+
+    /// Removes everything in `other`, a word at a time
+    pub fn difference_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a & !b);
+    }
+
+    fn zip_words(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        for (i, word) in self.words.iter_mut().enumerate() {
+            *word = op(*word, other.words.get(i).copied().unwrap_or(0));
+        }
+        self.trim();
+    }
+
+    /// Clears any bits past `len` in the last word
+    fn trim(&mut self) {
+        if !self.len.is_multiple_of(64) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (self.len % 64)) - 1;
+            }
+        }
+    }
+}
+
+impl Iterator for Ones<u64> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.word == 0 {
+            return None;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(self.base + bit)
+    }
+}
+
+/// One bit per item, `true` being in the set
+impl FromIterator<bool> for DynBitSet {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut set = DynBitSet::default();
+        for value in iter {
+            if set.len % 64 == 0 {
+                set.words.push(0);
+            }
+            set.len += 1;
+            set.set(set.len - 1, value);
+        }
+        set
+    }
+}
+
+impl fmt::Debug for DynBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Not for &DynBitSet {
+    type Output = DynBitSet;
+    fn not(self) -> DynBitSet {
+        let mut set = DynBitSet { words: self.words.iter().map(|word| !word).collect(), len: self.len };
+        set.trim();
+        set
+    }
+}
+
+/// Implements a word level operator for borrowed `DynBitSet`s
+macro_rules! dyn_bitset_op {
+    ($op:ident, $method:ident, $with:ident) => {
+        impl $op for &DynBitSet {
+            type Output = DynBitSet;
+            fn $method(self, other: &DynBitSet) -> DynBitSet {
+                let mut set = self.clone();
+                set.$with(other);
+                set
+            }
+        }
+    };
+}
+
+dyn_bitset_op!(BitAnd, bitand, and_with);
+dyn_bitset_op!(BitOr, bitor, or_with);
+dyn_bitset_op!(BitXor, bitxor, xor_with);
+dyn_bitset_op!(Sub, sub, difference_with);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_and_columns() {
+        let row: BitSet<25> = BitSet::row(1, 5);
+        assert_eq!(row.bits(), 0b11111 << 5);
+        let column: BitSet<25> = BitSet::column(2, 5, 5);
+        assert_eq!(column.iter().collect::<Vec<_>>(), vec![2, 7, 12, 17, 22]);
+        assert_eq!((row & column).iter().collect::<Vec<_>>(), vec![7]);
+        assert_eq!((row | column).count_ones(), 9);
+    }
+
+    #[test]
+    fn operators_stay_below_n() {
+        let a: BitSet<7> = [0, 2, 6].into_iter().collect();
+        let b: BitSet<7> = [2, 3].into_iter().collect();
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), vec![0, 3, 6]);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![0, 6]);
+        assert_eq!((!a).iter().collect::<Vec<_>>(), vec![1, 3, 4, 5]);
+        assert_eq!((a << 1).iter().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!((a >> 2).iter().collect::<Vec<_>>(), vec![0, 4]);
+        assert_eq!(a << 200, BitSet::new());
+        assert_eq!(BitSet::<7>::full().count_ones(), 7);
+        assert_eq!(BitSet::<128>::full().count_ones(), 128);
+        assert!(b.is_subset(&(a | b)) && !b.is_subset(&a));
+    }
+
+    #[test]
+    fn dyn_operators_across_words() {
+        let a: DynBitSet = (0..130).map(|i| i % 3 == 0).collect();
+        let mut b = DynBitSet::with_len(130);
+        for i in [0, 64, 65, 129] {
+            b.insert(i);
+        }
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![0, 129]);
+        assert_eq!((&a | &b).count_ones(), a.count_ones() + 2);
+        assert_eq!((&a ^ &b).count_ones(), a.count_ones());
+        assert_eq!((&b - &a).iter().collect::<Vec<_>>(), vec![64, 65]);
+        // the unused bits of the last word stay clear
+        assert_eq!((!&a).count_ones(), 130 - a.count_ones());
+        assert!(b.contains(129) && !b.contains(128));
+    }
+}
@@ -0,0 +1,122 @@
+//! Checkpoints for step-by-step simulations, so a long run can be stopped
+//! and picked up again where it left off.
+//!
+//! ```ignore
+//! let checkpoints = Checkpoints::from_args("day-6", input.text());
+//! let (mut day, mut school) = checkpoints.resume().unwrap_or((0, school));
+//! while day < 256 {
+//!     school.next_day();
+//!     day += 1;
+//!     checkpoints.reached(day, &school);
+//! }
+//! ```
+//!
+//! `--checkpoint-every <steps>` saves the state to `<day>.checkpoint` (or
+//! `--checkpoint <file>`) every that many steps, `--resume` starts from the
+//! saved step instead of the beginning. Nothing is written without them.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::hash::fnv1a;
+
+const MAGIC: &str = "aoc checkpoint";
+
+/// Simulation state that can be written out as text and read back
+pub trait State: Sized {
+    fn save(&self) -> String;
+    fn restore(text: &str) -> Result<Self, String>;
+}
+
+pub struct Checkpoints {
+    path: PathBuf,
+    every: usize,
+    resume: bool,
+    input: u64,
+}
+
+impl Checkpoints {
+    /// Reads `--checkpoint-every`, `--checkpoint` and `--resume` from our
+    /// arguments, checkpointing the simulation `name` of `input`
+    pub fn from_args(name: &str, input: &str) -> Self {
+        let mut every = 0;
+        let mut path = None;
+        let mut resume = false;
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--resume" => resume = true,
+                "--checkpoint-every" => every = args.next().and_then(|steps| steps.parse().ok()).expect("--checkpoint-every takes a number of steps"),
+                "--checkpoint" => path = Some(PathBuf::from(args.next().expect("--checkpoint takes a file"))),
+                _ => (),
+            }
+        }
+        Checkpoints::new(name, path, every, resume, input)
+    }
+
+    /// For days parsing their own arguments, `every` of 0 never saves
+    pub fn new(name: &str, path: Option<PathBuf>, every: usize, resume: bool, input: &str) -> Self {
+        Checkpoints {
+            path: path.unwrap_or_else(|| PathBuf::from(format!("{}.checkpoint", name))),
+            every,
+            resume,
+            input: fnv1a(input.as_bytes()),
+        }
+    }
+
+    /// The saved step and state when resuming, otherwise `None` to start
+    /// from the beginning, as do checkpoints that can't be used
+    pub fn resume<S: State>(&self) -> Option<(usize, S)> {
+        if !self.resume {
+            return None;
+        }
+        match fs::read_to_string(&self.path).map_err(|e| e.to_string()).and_then(|text| self.parse(&text)) {
+            Ok((step, state)) => {
+                crate::info!("resuming from step {} saved in {}", step, self.path.display());
+                Some((step, state))
+            }
+            Err(e) => {
+                crate::warn!("not resuming from {}: {}, starting from the beginning", self.path.display(), e);
+                None
+            }
+        }
+    }
+
+    fn parse<S: State>(&self, text: &str) -> Result<(usize, S), String> {
+        let mut header = text.splitn(4, '\n');
+        if header.next().map(|line| line.trim_end()) != Some(MAGIC) {
+            return Err("not a checkpoint".to_string());
+        }
+        let input = header.next().and_then(|line| line.trim_end().strip_prefix("input "));
+        if input != Some(format!("{:016x}", self.input).as_str()) {
+            return Err("saved for a different input".to_string());
+        }
+        let step = header
+            .next()
+            .and_then(|line| line.trim_end().strip_prefix("step "))
+            .and_then(|step| step.parse().ok())
+            .ok_or("missing its step")?;
+        let state = S::restore(header.next().unwrap_or(""))?;
+        Ok((step, state))
+    }
+
+    /// Called after each step, saving the state on every `--checkpoint-every`th
+    pub fn reached<S: State>(&self, step: usize, state: &S) {
+        if self.every > 0 && step.is_multiple_of(self.every) {
+            if let Err(e) = self.save(step, state) {
+                crate::warn!("couldn't save checkpoint {}: {}", self.path.display(), e);
+            }
+        }
+    }
+
+    fn save<S: State>(&self, step: usize, state: &S) -> std::io::Result<()> {
+        let text = format!("{}\ninput {:016x}\nstep {}\n{}", MAGIC, self.input, step, state.save());
+        // written aside then renamed over, so a run killed mid-write keeps the last one
+        let partial = self.path.with_extension("partial");
+        fs::write(&partial, text)?;
+        fs::rename(&partial, &self.path)?;
+        crate::debug!("saved step {} to {}", step, self.path.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+//! 2D and 3D vectors over any primitive number, with the usual operators,
+//! distances, bounding boxes and the 24 rotations of 3D space.
+//!
+//! ```ignore
+//! let step = (to - from).signum();
+//! let location = a - Rotation::ALL[r] * b;
+//! let hit = Bounds::new(Vec2::new(20, -10), Vec2::new(30, -5)).contains(probe);
+//! ```
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Primitive numbers usable as vector components
+pub trait Number: Copy + Default + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    /// `|self - other|`, without overflowing for unsigned types
+    fn distance(self, other: Self) -> Self;
+    /// -1, 0 or 1, unsigned types only giving 0 or 1
+    fn signum(self) -> Self;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! number {
+    ($signum:expr => $($t:ty),*) => {$(
+        impl Number for $t {
+            fn distance(self, other: Self) -> Self {
+                if self > other { self - other } else { other - self }
+            }
+            fn signum(self) -> Self {
+                let signum: fn($t) -> $t = $signum;
+                signum(self)
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+number!(|n| n.signum() => i8, i16, i32, i64, i128, isize);
+// float signum is 1 for 0.0 and -1 for -0.0
+number!(|n| if n == 0.0 { 0.0 } else { n.signum() } => f32, f64);
+number!(|n| (n > 0) as _ => u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Implements the operators and shared methods once for both vector sizes
+macro_rules! vector {
+    ($name:ident { $($field:ident),+ }) => {
+        impl<T: Number> $name<T> {
+            pub fn new($($field: T),+) -> Self {
+                $name { $($field),+ }
+            }
+
+            /// Sum of the distances along each axis
+            pub fn manhattan(self, other: Self) -> T {
+                T::default() $(+ self.$field.distance(other.$field))+
+            }
+
+            /// Square of the straight line distance, exact for integers
+            pub fn distance_squared(self, other: Self) -> T {
+                T::default() $(+ self.$field.distance(other.$field) * self.$field.distance(other.$field))+
+            }
+
+            /// Straight line distance
+            pub fn euclidean(self, other: Self) -> f64 {
+                self.distance_squared(other).to_f64().sqrt()
+            }
+
+            /// Each component's sign, the unit step from one point towards another
+            /// along a horizontal, vertical or diagonal line
+            pub fn signum(self) -> Self {
+                $name { $($field: self.$field.signum()),+ }
+            }
+        }
+
+        impl<T: Number> Add for $name<T> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                $name { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Number> Sub for $name<T> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                $name { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Number + Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Number> Mul<T> for $name<T> {
+            type Output = Self;
+            fn mul(self, scale: T) -> Self {
+                $name { $($field: self.$field * scale),+ }
+            }
+        }
+
+        impl<T: Number> AddAssign for $name<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Number> SubAssign for $name<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Number> Components for $name<T> {
+            fn min_components(self, other: Self) -> Self {
+                $name { $($field: if other.$field < self.$field { other.$field } else { self.$field }),+ }
+            }
+            fn max_components(self, other: Self) -> Self {
+                $name { $($field: if other.$field > self.$field { other.$field } else { self.$field }),+ }
+            }
+            fn all_le(self, other: Self) -> bool {
+                true $(&& self.$field <= other.$field)+
+            }
+        }
+    };
+}
+
+vector!(Vec2 { x, y });
+vector!(Vec3 { x, y, z });
+
+impl<T: fmt::Display> fmt::Display for Vec2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vec3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Componentwise comparisons, what [`Bounds`] needs from a vector. Kept
+/// apart from the derived `Ord`, which compares lexicographically.
+pub trait Components: Copy {
+    /// Smallest of each component
+    fn min_components(self, other: Self) -> Self;
+    /// Largest of each component
+    fn max_components(self, other: Self) -> Self;
+    /// Whether every component is at most the other's
+    fn all_le(self, other: Self) -> bool;
+}
+
+/// Axis aligned box, inclusive of both corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<V> {
+    pub min: V,
+    pub max: V,
+}
+
+impl<V: Components> Bounds<V> {
+    /// Box spanning two opposite corners given in any order
+    pub fn new(a: V, b: V) -> Self {
+        Bounds { min: a.min_components(b), max: a.max_components(b) }
+    }
+
+    /// Smallest box holding every point, `None` when there are none
+    pub fn from_points(points: impl IntoIterator<Item = V>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds { min: first, max: first }, |bounds, point| bounds.extend(point)))
+    }
+
+    /// Grown to hold `point` as well
+    pub fn extend(self, point: V) -> Self {
+        Bounds { min: self.min.min_components(point), max: self.max.max_components(point) }
+    }
+
+    pub fn contains(&self, point: V) -> bool {
+        self.min.all_le(point) && point.all_le(self.max)
+    }
+}
+
+/// One of the 24 ways of turning 3D space onto itself, a signed
+/// permutation matrix with determinant 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation(pub [[i32; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// Every rotation, starting with the identity
+    pub const ALL: [Rotation; 24] = Rotation::all();
+
+    const fn all() -> [Rotation; 24] {
+        const PERMUTATIONS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+        let mut all = [Rotation::IDENTITY; 24];
+        let mut found = 0;
+        let mut p = 0;
+        while p < PERMUTATIONS.len() {
+            let mut signs = 0;
+            while signs < 8 {
+                let mut m = [[0; 3]; 3];
+                let mut row = 0;
+                while row < 3 {
+                    m[row][PERMUTATIONS[p][row]] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                    row += 1;
+                }
+                let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+                if det == 1 {
+                    all[found] = Rotation(m);
+                    found += 1;
+                }
+                signs += 1;
+            }
+            p += 1;
+        }
+        all
+    }
+
+    /// Undoes this rotation, the transpose of its matrix
+    pub fn inverse(self) -> Rotation {
+        let m = self.0;
+        Rotation([[m[0][0], m[1][0], m[2][0]], [m[0][1], m[1][1], m[2][1]], [m[0][2], m[1][2], m[2][2]]])
+    }
+}
+
+impl<T: Number> Mul<Vec3<T>> for Rotation {
+    type Output = Vec3<T>;
+
+    fn mul(self, v: Vec3<T>) -> Vec3<T> {
+        // entries are only ever -1, 0 or 1, so add or subtract components
+        // rather than needing to convert the entries into T
+        let components = [v.x, v.y, v.z];
+        let row = |r: usize| {
+            (0..3).fold(T::default(), |acc, c| match self.0[r][c] {
+                1 => acc + components[c],
+                -1 => acc - components[c],
+                _ => acc,
+            })
+        };
+        Vec3 { x: row(0), y: row(1), z: row(2) }
+    }
+}
+
+impl Mul for Rotation {
+    type Output = Rotation;
+
+    /// Applies `other` first, then `self`
+    fn mul(self, other: Rotation) -> Rotation {
+        let (a, b) = (self.0, other.0);
+        let mut m = [[0; 3]; 3];
+        for (r, row) in m.iter_mut().enumerate() {
+            for (c, entry) in row.iter_mut().enumerate() {
+                *entry = (0..3).map(|k| a[r][k] * b[k][c]).sum();
+            }
+        }
+        Rotation(m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signum_is_zero_along_an_axis() {
+        assert_eq!((Vec2::new(3, 3) - Vec2::new(3, 7)).signum(), Vec2::new(0, -1));
+        assert_eq!((Vec2::new(5.0, 2.5) - Vec2::new(1.0, 2.5)).signum(), Vec2::new(1.0, 0.0));
+        assert_eq!(Vec3::new(-0.0f32, 0.0, -2.0).signum(), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(Vec2::new(0u8, 9).signum(), Vec2::new(0, 1));
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Vec3::new(1105, -1205, 1229).manhattan(Vec3::new(-92, -2380, -20)), 3621);
+        assert_eq!(Vec2::new(3u32, 10).manhattan(Vec2::new(7, 2)), 12);
+        assert_eq!(Vec2::new(0, 0).distance_squared(Vec2::new(3, -4)), 25);
+        assert_eq!(Vec2::new(0.0, 0.0).euclidean(Vec2::new(3.0, 4.0)), 5.0);
+    }
+
+    #[test]
+    fn bounds() {
+        let target = Bounds::new(Vec2::new(30, -5), Vec2::new(20, -10));
+        assert_eq!((target.min, target.max), (Vec2::new(20, -10), Vec2::new(30, -5)));
+        assert!(target.contains(Vec2::new(20, -5)) && target.contains(Vec2::new(30, -10)) && target.contains(Vec2::new(25, -7)));
+        assert!(!target.contains(Vec2::new(31, -7)) && !target.contains(Vec2::new(25, -4)));
+
+        let points = [Vec3::new(1, 5, -2), Vec3::new(-3, 0, 4), Vec3::new(2, 2, 2)];
+        let bounds = Bounds::from_points(points).unwrap();
+        assert_eq!(bounds, Bounds { min: Vec3::new(-3, 0, -2), max: Vec3::new(2, 5, 4) });
+        assert!(points.iter().all(|&point| bounds.contains(point)));
+        assert_eq!(Bounds::<Vec2<i32>>::from_points([]), None);
+    }
+
+    #[test]
+    fn rotations() {
+        let det = |m: [[i32; 3]; 3]| {
+            m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+        };
+        assert_eq!(Rotation::ALL[0], Rotation::IDENTITY);
+        let distinct: std::collections::HashSet<Rotation> = Rotation::ALL.into_iter().collect();
+        assert_eq!(distinct.len(), 24);
+        let v = Vec3::new(1, 2, 3);
+        for rotation in Rotation::ALL {
+            assert_eq!(det(rotation.0), 1);
+            assert_eq!(rotation * rotation.inverse(), Rotation::IDENTITY);
+            assert_eq!(rotation.inverse() * rotation, Rotation::IDENTITY);
+            assert_eq!(rotation.inverse() * (rotation * v), v);
+            assert_eq!((rotation * v).manhattan(Vec3::default()), 6);
+        }
+        // closed under composition
+        assert!(Rotation::ALL.iter().all(|&a| Rotation::ALL.iter().all(|&b| distinct.contains(&(a * b)))));
+    }
+}
@@ -0,0 +1,392 @@
+//! Graph searches over nodes numbered `0..len`, with edges given either by
+//! a neighbour function (grids, computed on the fly) or an [`Adjacency`] list.
+//!
+//! ```ignore
+//! let fill = graph::bfs(rows * cols, low_points, |v| graph::grid_neighbors(v, rows, cols));
+//! let path = graph::dijkstra(rows * cols, 0, rows * cols - 1, |v| grid_neighbors(v, rows, cols).map(|n| (n, risk[n])));
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// Marks a node without a predecessor
+const NONE: usize = usize::MAX;
+
+/// Explicit adjacency list with a label per node
+#[derive(Debug, Clone)]
+pub struct Adjacency<L> {
+    labels: Vec<L>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<L: PartialEq> Default for Adjacency<L> {
+    fn default() -> Self {
+        Adjacency { labels: vec!(), edges: vec!() }
+    }
+}
+
+impl<L: PartialEq> Adjacency<L> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the node with `label`, adding it the first time it is seen
+    pub fn node(&mut self, label: L) -> usize {
+        match self.find(&label) {
+            Some(idx) => idx,
+            None => {
+                self.labels.push(label);
+                self.edges.push(vec!());
+                self.labels.len() - 1
+            }
+        }
+    }
+
+    pub fn find(&self, label: &L) -> Option<usize> {
+        self.labels.iter().position(|other| other == label)
+    }
+
+    pub fn label(&self, node: usize) -> &L {
+        &self.labels[node]
+    }
+
+    pub fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.edges[from].push(to);
+    }
+
+    pub fn add_undirected(&mut self, a: usize, b: usize) {
+        self.add_edge(a, b);
+        self.add_edge(b, a);
+    }
+
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges[node].iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+/// The up to four cells beside `node` in a `rows` x `cols` grid stored row by row
+pub fn grid_neighbors(node: usize, rows: usize, cols: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (node / cols, node % cols);
+    [
+        (row > 0).then(|| node - cols),
+        (col > 0).then(|| node - 1),
+        (col + 1 < cols).then(|| node + 1),
+        (row + 1 < rows).then(|| node + cols),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// A node reached by [`bfs`] or [`dfs`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Visit {
+    pub node: usize,
+    /// Edges from the start it was reached from
+    pub depth: usize,
+    /// Position of that start among the ones given
+    pub start: usize,
+}
+
+/// Breadth first search from one or more starts at once, each node visited
+/// once from whichever start reaches it first
+pub struct Bfs<F> {
+    queue: VecDeque<Visit>,
+    seen: Vec<bool>,
+    prev: Vec<usize>,
+    neighbors: F,
+}
+
+pub fn bfs<F, I>(len: usize, starts: impl IntoIterator<Item = usize>, neighbors: F) -> Bfs<F>
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    let mut seen = vec![false; len];
+    let mut queue = VecDeque::new();
+    for (start, node) in starts.into_iter().enumerate() {
+        if !seen[node] {
+            seen[node] = true;
+            queue.push_back(Visit { node, depth: 0, start });
+        }
+    }
+    Bfs { queue, seen, prev: vec![NONE; len], neighbors }
+}
+
+impl<F> Bfs<F> {
+    /// Nodes from the start to `node`, once `node` has been visited
+    pub fn path_to(&self, node: usize) -> Vec<usize> {
+        reconstruct(&self.prev, node)
+    }
+}
+
+impl<F, I> Iterator for Bfs<F>
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    type Item = Visit;
+
+    fn next(&mut self) -> Option<Visit> {
+        let visit = self.queue.pop_front()?;
+        for next in (self.neighbors)(visit.node) {
+            if !self.seen[next] {
+                self.seen[next] = true;
+                self.prev[next] = visit.node;
+                self.queue.push_back(Visit { node: next, depth: visit.depth + 1, start: visit.start });
+            }
+        }
+        Some(visit)
+    }
+}
+
+/// Depth first search, visiting each node reachable from `start` once in preorder
+pub struct Dfs<F> {
+    stack: Vec<(Visit, usize)>,
+    seen: Vec<bool>,
+    prev: Vec<usize>,
+    neighbors: F,
+}
+
+pub fn dfs<F, I>(len: usize, start: usize, neighbors: F) -> Dfs<F>
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    Dfs { stack: vec![(Visit { node: start, depth: 0, start: 0 }, NONE)], seen: vec![false; len], prev: vec![NONE; len], neighbors }
+}
+
+impl<F> Dfs<F> {
+    /// Nodes from the start to `node` along the search tree, once `node` has been visited
+    pub fn path_to(&self, node: usize) -> Vec<usize> {
+        reconstruct(&self.prev, node)
+    }
+}
+
+impl<F, I> Iterator for Dfs<F>
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    type Item = Visit;
+
+    fn next(&mut self) -> Option<Visit> {
+        loop {
+            let (visit, from) = self.stack.pop()?;
+            if self.seen[visit.node] {
+                continue;
+            }
+            self.seen[visit.node] = true;
+            self.prev[visit.node] = from;
+
+            // pushed in reverse so neighbours are explored in the order given
+            let mut next: Vec<usize> = (self.neighbors)(visit.node).into_iter().filter(|next| !self.seen[*next]).collect();
+            next.reverse();
+            for node in next {
+                self.stack.push((Visit { node, depth: visit.depth + 1, start: 0 }, visit.node));
+            }
+            return Some(visit);
+        }
+    }
+}
+
+/// Counts every walk from `start` to `goal`, where `enter` decides whether
+/// the walk so far may step onto a node, so nodes can be revisited
+pub fn count_walks<F, I, E>(start: usize, goal: usize, mut neighbors: F, mut enter: E) -> usize
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = usize>,
+    E: FnMut(&[usize], usize) -> bool,
+{
+    fn walk<F, I, E>(walk_so_far: &mut Vec<usize>, goal: usize, neighbors: &mut F, enter: &mut E) -> usize
+    where
+        F: FnMut(usize) -> I,
+        I: IntoIterator<Item = usize>,
+        E: FnMut(&[usize], usize) -> bool,
+    {
+        let node = *walk_so_far.last().unwrap();
+        if node == goal {
+            return 1;
+        }
+        let next: Vec<usize> = neighbors(node).into_iter().collect();
+        let mut walks = 0;
+        for next in next {
+            if enter(walk_so_far, next) {
+                walk_so_far.push(next);
+                walks += walk(walk_so_far, goal, neighbors, enter);
+                walk_so_far.pop();
+            }
+        }
+        walks
+    }
+
+    walk(&mut vec![start], goal, &mut neighbors, &mut enter)
+}
+
+/// Cheapest path found by [`dijkstra`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub cost: usize,
+    /// From the start through to the goal
+    pub nodes: Vec<usize>,
+}
+
+/// Cheapest path from `start` to `goal`, with `neighbors` giving each
+/// neighbour along with the cost of stepping onto it
+pub fn dijkstra<F, I>(len: usize, start: usize, goal: usize, mut neighbors: F) -> Option<Path>
+where
+    F: FnMut(usize) -> I,
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let mut dist: Vec<usize> = vec![usize::MAX; len];
+    let mut prev: Vec<usize> = vec![NONE; len];
+    let mut queue: BinaryHeap<Reverse<(usize, usize)>> = BinaryHeap::new();
+
+    dist[start] = 0;
+    queue.push(Reverse((0, start)));
+    while let Some(Reverse((cost, node))) = queue.pop() {
+        if node == goal {
+            return Some(Path { cost, nodes: reconstruct(&prev, goal) });
+        }
+        if cost > dist[node] {
+            continue; // already reached more cheaply
+        }
+        for (next, step) in neighbors(node) {
+            let alt = cost + step;
+            if alt < dist[next] {
+                dist[next] = alt;
+                prev[next] = node;
+                queue.push(Reverse((alt, next)));
+            }
+        }
+    }
+    None
+}
+
+/// Follows predecessors back from `node` to the start, returned start first
+pub fn reconstruct(prev: &[usize], node: usize) -> Vec<usize> {
+    let mut path = vec![node];
+    let mut cur = node;
+    while prev[cur] != NONE {
+        cur = prev[cur];
+        path.push(cur);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The small cave system from day 12's example
+    fn caves() -> (Adjacency<&'static str>, usize, usize) {
+        let mut caves = Adjacency::new();
+        for (a, b) in [("start", "A"), ("start", "b"), ("A", "c"), ("A", "b"), ("b", "d"), ("A", "end"), ("b", "end")] {
+            let (a, b) = (caves.node(a), caves.node(b));
+            caves.add_undirected(a, b);
+        }
+        let (start, end) = (caves.find(&"start").unwrap(), caves.find(&"end").unwrap());
+        (caves, start, end)
+    }
+
+    #[test]
+    fn count_walks_visiting_small_caves_once() {
+        let (caves, start, end) = caves();
+        let big = |node: usize| caves.label(node).chars().all(|c| c.is_ascii_uppercase());
+        let walks = count_walks(start, end, |node| caves.neighbors(node), |walk, next| big(next) || !walk.contains(&next));
+        assert_eq!(walks, 10);
+    }
+
+    #[test]
+    fn count_walks_with_one_small_cave_twice() {
+        let (caves, start, end) = caves();
+        let big = |node: usize| caves.label(node).chars().all(|c| c.is_ascii_uppercase());
+        let walks = count_walks(start, end, |node| caves.neighbors(node), |walk, next| {
+            let twice = walk.iter().enumerate().any(|(i, node)| !big(*node) && walk[i + 1..].contains(node));
+            big(next) || !walk.contains(&next) || (next != start && !twice)
+        });
+        assert_eq!(walks, 36);
+    }
+
+    #[test]
+    fn bfs_from_several_starts() {
+        // a 3 x 4 grid, from its opposite corners
+        let visits: Vec<Visit> = bfs(12, [0, 11], |node| grid_neighbors(node, 3, 4)).collect();
+        assert_eq!(visits.len(), 12);
+        assert_eq!(&visits[..2], &[Visit { node: 0, depth: 0, start: 0 }, Visit { node: 11, depth: 0, start: 1 }]);
+        let visit = |node: usize| *visits.iter().find(|visit| visit.node == node).unwrap();
+        assert_eq!(visit(5), Visit { node: 5, depth: 2, start: 0 });
+        assert_eq!(visit(7), Visit { node: 7, depth: 1, start: 1 });
+        assert_eq!(visit(6), Visit { node: 6, depth: 2, start: 1 });
+        assert!(visits.windows(2).all(|pair| pair[0].depth <= pair[1].depth));
+    }
+
+    #[test]
+    fn bfs_paths() {
+        let mut search = bfs(12, [0], |node| grid_neighbors(node, 3, 4));
+        search.by_ref().for_each(drop);
+        assert_eq!(search.path_to(5), vec![0, 1, 5]);
+        assert_eq!(search.path_to(11), vec![0, 1, 2, 3, 7, 11]);
+        assert_eq!(search.path_to(0), vec![0]);
+    }
+
+    #[test]
+    fn dfs_follows_neighbours_in_order() {
+        let mut graph = Adjacency::new();
+        let (a, b, c, d) = (graph.node('a'), graph.node('b'), graph.node('c'), graph.node('d'));
+        graph.add_edge(a, c);
+        graph.add_edge(a, b);
+        graph.add_edge(c, d);
+        graph.add_edge(b, d);
+        let order: Vec<char> = dfs(graph.len(), a, |node| graph.neighbors(node)).map(|visit| *graph.label(visit.node)).collect();
+        assert_eq!(order, vec!['a', 'c', 'd', 'b']);
+
+        // a 2 x 3 grid snakes right along the top and back along the bottom
+        let mut search = dfs(6, 0, |node| grid_neighbors(node, 2, 3));
+        let visits: Vec<(usize, usize)> = search.by_ref().map(|visit| (visit.node, visit.depth)).collect();
+        assert_eq!(visits, vec![(0, 0), (1, 1), (2, 2), (5, 3), (4, 4), (3, 5)]);
+        assert_eq!(search.path_to(3), vec![0, 1, 2, 5, 4, 3]);
+    }
+
+    #[test]
+    fn reconstruct_follows_predecessors() {
+        assert_eq!(reconstruct(&[NONE, 0, 1, 1], 3), vec![0, 1, 3]);
+        assert_eq!(reconstruct(&[NONE, 0, 1, 1], 0), vec![0]);
+    }
+
+    #[test]
+    fn dijkstra_finds_the_lowest_risk() {
+        // day 15's example
+        let risk: Vec<usize> = "1163751742 1381373672 2136511328 3694931569 7463417111 1319128137 1359912421 3125421639 1293138521 2311944581"
+            .bytes()
+            .filter(|b| b.is_ascii_digit())
+            .map(|b| (b - b'0') as usize)
+            .collect();
+        let path = dijkstra(100, 0, 99, |node| grid_neighbors(node, 10, 10).map(|next| (next, risk[next]))).unwrap();
+        assert_eq!(path.cost, 40);
+        assert_eq!((path.nodes[0], path.nodes[path.nodes.len() - 1]), (0, 99));
+        assert_eq!(path.nodes[1..].iter().map(|&node| risk[node]).sum::<usize>(), 40);
+    }
+
+    #[test]
+    fn dijkstra_without_a_path() {
+        let mut graph: Adjacency<u8> = Adjacency::new();
+        let (a, b, c) = (graph.node(0), graph.node(1), graph.node(2));
+        graph.add_edge(a, b);
+        graph.add_edge(c, a);
+        assert_eq!(dijkstra(graph.len(), a, c, |node| graph.neighbors(node).map(|next| (next, 1))), None);
+        assert_eq!(dijkstra(graph.len(), a, b, |node| graph.neighbors(node).map(|next| (next, 1))), Some(Path { cost: 1, nodes: vec![a, b] }));
+    }
+}
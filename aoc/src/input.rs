@@ -0,0 +1,133 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Puzzle input read once into a single buffer, handing out borrowed
+/// lines and blank-line separated sections
+#[derive(Debug, Clone)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    /// Reads all of stdin, the way every day receives its `input.txt`
+    pub fn from_stdin() -> Self {
+        let mut text = String::new();
+        io::stdin().lock().read_to_string(&mut text).expect("stdin error");
+        Input { text }
+    }
+
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        Ok(Input { text: fs::read_to_string(path)? })
+    }
+
+    /// The whole input
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Every line, blank ones included, without `\n` or `\r\n` endings
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// First line, for the single line puzzles
+    pub fn first_line(&self) -> &str {
+        self.lines().next().unwrap_or("")
+    }
+
+    /// Blocks of lines separated by one or more blank lines, each block
+    /// without its trailing line ending
+    pub fn sections(&self) -> Sections<'_> {
+        Sections { rest: &self.text }
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Input { text }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Input { text: text.to_string() }
+    }
+}
+
+/// Iterator over the blank-line separated blocks of an [`Input`]
+pub struct Sections<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        // skip leading blank lines
+        loop {
+            let (line, rest) = split_line(self.rest);
+            if self.rest.is_empty() || !line.trim().is_empty() {
+                break;
+            }
+            self.rest = rest;
+        }
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        // take lines until the next blank one
+        let start = self.rest;
+        let mut len = 0;
+        while !self.rest.is_empty() {
+            let (line, rest) = split_line(self.rest);
+            if line.trim().is_empty() {
+                break;
+            }
+            len = start.len() - self.rest.len() + line.len();
+            self.rest = rest;
+        }
+        Some(start[..len].trim_end_matches('\r'))
+    }
+}
+
+/// Splits off the first line, returning it without its line ending
+fn split_line(text: &str) -> (&str, &str) {
+    match text.find('\n') {
+        Some(end) => (&text[..end], &text[end + 1..]),
+        None => (text, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_without_endings() {
+        let input = Input::from("199\r\n200\n\n208\r\n");
+        assert_eq!(input.lines().collect::<Vec<_>>(), vec!["199", "200", "", "208"]);
+        assert_eq!(input.first_line(), "199");
+        assert_eq!(Input::from("").first_line(), "");
+    }
+
+    #[test]
+    fn sections_between_blank_lines() {
+        let input = Input::from("\n7,4,9\n\n\n\n22 13\n 8  2\n  \n3 15\n\n\n");
+        assert_eq!(input.sections().collect::<Vec<_>>(), vec!["7,4,9", "22 13\n 8  2", "3 15"]);
+    }
+
+    #[test]
+    fn sections_with_crlf() {
+        let input = Input::from("a\r\nb\r\n\r\n\r\nc\r\n\r\n");
+        let sections: Vec<&str> = input.sections().collect();
+        assert_eq!(sections, vec!["a\r\nb", "c"]);
+        assert_eq!(sections[0].lines().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn no_sections_in_blank_input() {
+        assert_eq!(Input::from("\n \r\n\n").sections().count(), 0);
+        assert_eq!(Input::from("last").sections().collect::<Vec<_>>(), vec!["last"]);
+    }
+}
@@ -0,0 +1,14 @@
+//! Shared helpers for the daily puzzle crates
+
+pub mod bitset;
+pub mod checkpoint;
+pub mod geometry;
+pub mod graph;
+pub mod hash;
+pub mod input;
+pub mod log;
+pub mod parse;
+pub mod progress;
+pub mod scrape;
+pub mod solutions;
+pub mod trace;
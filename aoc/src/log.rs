@@ -0,0 +1,122 @@
+//! Leveled logging to stderr, keeping stdout for the answers.
+//!
+//! Messages at `warn` and above are shown by default, each `-v` on the
+//! command line shows one more level (`-v` info, `-vv` debug, `-vvv` trace)
+//! and `-q` only shows errors. `AOC_LOG=<level>` sets the starting level.
+
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Level> {
+        Level::ALL.into_iter().find(|level| level.name().eq_ignore_ascii_case(name))
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+/// Sets the level from `AOC_LOG` then the `-v`/`-q` flags in our arguments
+pub fn init() {
+    if let Some(level) = env::var("AOC_LOG").ok().and_then(|name| Level::from_name(&name)) {
+        set_level(level);
+    }
+    let mut verbose: u8 = 0;
+    let mut quiet = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "-q" | "--quiet" => quiet = true,
+            "--verbose" => verbose += 1,
+            flags if flags.len() > 1 && flags.starts_with('-') && flags[1..].chars().all(|c| c == 'v') => {
+                verbose += (flags.len() - 1) as u8;
+            }
+            _ => (),
+        }
+    }
+    set_verbosity(verbose, quiet);
+}
+
+/// Applies already parsed `-v` occurrences and `-q`, for days parsing their own arguments
+pub fn set_verbosity(verbose: u8, quiet: bool) {
+    if quiet {
+        set_level(Level::Error);
+    } else if verbose > 0 {
+        let level = (level() as usize + verbose as usize).min(Level::Trace as usize);
+        set_level(Level::ALL[level]);
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::ALL[LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+/// Whether messages at `level` are shown, for skipping expensive output entirely
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, args: fmt::Arguments) {
+    // off the progress bar's line, which is redrawn on its next update
+    crate::progress::clear();
+    eprintln!("[{}] {}", level.name(), args);
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
@@ -0,0 +1,256 @@
+//! A tiny parser combinator kit for puzzle input formats.
+//!
+//! Parsers are functions from the remaining input to a value and what is
+//! left over, combined with the helpers here and run with [`parse`], which
+//! requires the whole text to be consumed and reports failures with their
+//! line and column.
+//!
+//! ```ignore
+//! use aoc::parse::{self, integer, literal, preceded, separated_pair};
+//!
+//! let range = |axis| preceded(literal(axis), separated_pair(integer::<i32>(), literal(".."), integer::<i32>()));
+//! let target = preceded(literal("target area: "), separated_pair(range("x="), literal(", "), range("y=")));
+//! let ((x1, x2), (y1, y2)) = parse::parse(target, "target area: x=20..30, y=-10..-5")?;
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The parsed value and the input left after it, or what was expected
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Where a parser gave up, and what it wanted to see there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    pub expected: String,
+    pub rest: &'a str,
+}
+
+fn fail<'a, T>(expected: impl Into<String>, rest: &'a str) -> PResult<'a, T> {
+    Err(Failure { expected: expected.into(), rest })
+}
+
+/// Anything usable as a parser, mostly the closures returned by this module
+pub trait Parser<'a, T>: Fn(&'a str) -> PResult<'a, T> {}
+
+impl<'a, T, F: Fn(&'a str) -> PResult<'a, T>> Parser<'a, T> for F {}
+
+/// A failed [`parse`], positioned within the text given to it
+#[derive(Clone, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}, found {}", self.line, self.column, self.expected, self.found)
+    }
+}
+
+// the same as Display, so `unwrap()` in a day reads well
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+    fn new(text: &str, failure: Failure) -> Self {
+        let offset = text.len() - failure.rest.len();
+        let before = &text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+
+        let found = failure.rest.lines().next().unwrap_or("");
+        let found = match found.chars().count() {
+            0 if failure.rest.is_empty() => "end of input".to_string(),
+            0 => "end of line".to_string(),
+            n if n > 20 => format!("{:?}...", found.chars().take(20).collect::<String>()),
+            _ => format!("{:?}", found),
+        };
+        Error { line, column, expected: failure.expected, found }
+    }
+}
+
+/// Runs `parser` over all of `text`, ignoring trailing line breaks
+pub fn parse<'a, T>(parser: impl Parser<'a, T>, text: &'a str) -> Result<T, Error> {
+    match parser(text) {
+        Ok((value, rest)) if rest.trim_end_matches(['\r', '\n']).is_empty() => Ok(value),
+        Ok((_, rest)) => Err(Error::new(text, Failure { expected: "end of input".to_string(), rest })),
+        Err(failure) => Err(Error::new(text, failure)),
+    }
+}
+
+/// Exactly `expected`
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(expected) {
+        Some(rest) => Ok((&input[..expected.len()], rest)),
+        None => fail(format!("{:?}", expected), input),
+    }
+}
+
+/// A decimal integer with an optional sign
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let sign = input.starts_with(['-', '+']) as usize;
+        let digits = input[sign..].chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return fail("an integer", input);
+        }
+        match input[..sign + digits].parse::<T>() {
+            Ok(value) => Ok((value, &input[sign + digits..])),
+            Err(_) => fail("an integer in range", input),
+        }
+    }
+}
+
+/// One or more letters
+pub fn alpha<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let len: usize = input.chars().take_while(|c| c.is_alphabetic()).map(|c| c.len_utf8()).sum();
+        match len {
+            0 => fail("letters", input),
+            _ => Ok((&input[..len], &input[len..])),
+        }
+    }
+}
+
+/// Any one of the characters in `chars`
+pub fn one_of<'a>(chars: &'static str) -> impl Parser<'a, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if chars.contains(c) => Ok((c, &input[c.len_utf8()..])),
+        _ => fail(format!("one of {:?}", chars), input),
+    }
+}
+
+/// A line break, `\n` or `\r\n`
+pub fn newline<'a>() -> impl Parser<'a, ()> {
+    move |input: &'a str| match input.strip_prefix("\r\n").or_else(|| input.strip_prefix('\n')) {
+        Some(rest) => Ok(((), rest)),
+        None => fail("a new line", input),
+    }
+}
+
+/// Applies `f` to the parsed value
+pub fn map<'a, A, B>(parser: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Parser<'a, B> {
+    move |input: &'a str| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// `a` then `b`, keeping both
+pub fn pair<'a, A, B>(a: impl Parser<'a, A>, b: impl Parser<'a, B>) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (first, rest) = a(input)?;
+        let (second, rest) = b(rest)?;
+        Ok(((first, second), rest))
+    }
+}
+
+/// `a`, `sep` then `b`, keeping `a` and `b`
+pub fn separated_pair<'a, A, S, B>(a: impl Parser<'a, A>, sep: impl Parser<'a, S>, b: impl Parser<'a, B>) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (first, rest) = a(input)?;
+        let (_, rest) = sep(rest)?;
+        let (second, rest) = b(rest)?;
+        Ok(((first, second), rest))
+    }
+}
+
+/// `prefix` then `parser`, keeping only the latter
+pub fn preceded<'a, P, T>(prefix: impl Parser<'a, P>, parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let (_, rest) = prefix(input)?;
+        parser(rest)
+    }
+}
+
+/// `parser` then `suffix`, keeping only the former
+pub fn terminated<'a, T, S>(parser: impl Parser<'a, T>, suffix: impl Parser<'a, S>) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let (value, rest) = parser(input)?;
+        let (_, rest) = suffix(rest)?;
+        Ok((value, rest))
+    }
+}
+
+/// One or more `item`s with `sep` between them, ending at the end of the
+/// input or a blank line. Otherwise once a separator has parsed the next
+/// item must too, so errors point at the broken item.
+pub fn separated<'a, T, S>(item: impl Parser<'a, T>, sep: impl Parser<'a, S>) -> impl Parser<'a, Vec<T>> {
+    let blank = newline();
+    move |input: &'a str| {
+        let (first, mut rest) = item(input)?;
+        let mut items = vec![first];
+        while let Ok((_, after_sep)) = sep(rest) {
+            match item(after_sep) {
+                Ok((next, after_item)) => {
+                    items.push(next);
+                    rest = after_item;
+                }
+                Err(failure) if failure.rest.len() == after_sep.len() && (after_sep.is_empty() || blank(after_sep).is_ok()) => break,
+                Err(failure) => return Err(failure),
+            }
+        }
+        Ok((items, rest))
+    }
+}
+
+/// One `item` per line
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(item, newline())
+}
+
+/// Blank line separated sections, each parsed by `section`
+pub fn sections<'a, T>(section: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    separated(section, pair(newline(), newline()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target<'a>() -> impl Parser<'a, ((i32, i32), (i32, i32))> {
+        let range = |axis| preceded(literal(axis), separated_pair(integer::<i32>(), literal(".."), integer::<i32>()));
+        preceded(literal("target area: "), separated_pair(range("x="), literal(", "), range("y=")))
+    }
+
+    #[test]
+    fn parses_a_whole_text() {
+        assert_eq!(parse(target(), "target area: x=20..30, y=-10..-5\n"), Ok(((20, 30), (-10, -5))));
+        let dot = separated_pair(integer::<u32>(), literal(","), integer::<u32>());
+        assert_eq!(parse(lines(dot), "6,10\r\n0,14\n9,10"), Ok(vec![(6, 10), (0, 14), (9, 10)]));
+    }
+
+    #[test]
+    fn errors_give_line_and_column() {
+        let error = parse(target(), "target area: x=20..30, q=-10..-5").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 24));
+        assert_eq!(error.to_string(), "line 1, column 24: expected \"y=\", found \"q=-10..-5\"");
+
+        let dot = separated_pair(integer::<u32>(), literal(","), integer::<u32>());
+        let error = parse(lines(dot), "6,10\n0,14\n9;10\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+        assert_eq!(error.expected, "\",\"");
+    }
+
+    #[test]
+    fn errors_at_the_end() {
+        let error = parse(lines(integer::<u8>()), "1\n2\n300").unwrap_err();
+        assert_eq!((error.line, error.column, error.expected.as_str()), (3, 1, "an integer in range"));
+
+        let error = parse(integer::<u8>(), "12 ").unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (3, "end of input"));
+        let error = parse(literal("ab"), "").unwrap_err();
+        assert_eq!(error.found, "end of input");
+    }
+
+    #[test]
+    fn sections_split_at_blank_lines() {
+        let section = lines(alpha());
+        assert_eq!(parse(sections(section), "ab\ncd\n\nef\n"), Ok(vec![vec!["ab", "cd"], vec!["ef"]]));
+    }
+}
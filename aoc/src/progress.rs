@@ -0,0 +1,124 @@
+//! Progress bars with an ETA for long running solvers, drawn on stderr
+//! only when it is a terminal, so piped runs and the runner see nothing.
+//!
+//! ```ignore
+//! let mut progress = Progress::new("normalising", scanners.len());
+//! progress.set(finished.len());
+//! ```
+//!
+//! Drawing is throttled and only starts once a solver has been going for a
+//! moment, so quick runs don't flicker. `-q` hides the bar too.
+
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::log::{self, Level};
+
+/// How long a solver runs before its bar first appears
+const DELAY: Duration = Duration::from_millis(250);
+/// Least time between redraws
+const REDRAW: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 30;
+
+/// Whether a bar is on screen, so log lines can clear it first
+static SHOWN: AtomicBool = AtomicBool::new(false);
+
+pub struct Progress {
+    label: String,
+    done: usize,
+    total: usize,
+    start: Instant,
+    last_draw: Option<Instant>,
+    enabled: bool,
+}
+
+impl Progress {
+    /// A bar for `total` items of work, an estimate that can be revised
+    pub fn new(label: &str, total: usize) -> Self {
+        Progress {
+            label: label.to_string(),
+            done: 0,
+            total,
+            start: Instant::now(),
+            last_draw: None,
+            enabled: io::stderr().is_terminal() && log::enabled(Level::Warn),
+        }
+    }
+
+    pub fn inc(&mut self, items: usize) {
+        self.set(self.done + items);
+    }
+
+    pub fn set(&mut self, done: usize) {
+        self.done = done;
+        if self.enabled {
+            self.tick();
+        }
+    }
+
+    /// Revises how much work there is in all
+    pub fn set_total(&mut self, total: usize) {
+        self.total = total;
+    }
+
+    /// Removes the bar, also done when it is dropped
+    pub fn finish(self) {}
+
+    fn tick(&mut self) {
+        let now = Instant::now();
+        let due = match self.last_draw {
+            Some(last) => now - last >= REDRAW,
+            None => now - self.start >= DELAY,
+        };
+        if due {
+            self.last_draw = Some(now);
+            self.draw(now - self.start);
+        }
+    }
+
+    fn draw(&self, elapsed: Duration) {
+        let fraction = match self.total {
+            0 => 0.0,
+            total => (self.done as f64 / total as f64).min(1.0),
+        };
+        let filled = (fraction * BAR_WIDTH as f64) as usize;
+        // past the estimate there's no telling how much is left
+        let eta = match self.done {
+            done if done > 0 && done < self.total => format_duration(elapsed.mul_f64((self.total - done) as f64 / done as f64)),
+            _ => "?".to_string(),
+        };
+        let mut stderr = io::stderr().lock();
+        let _ = write!(
+            stderr,
+            "\r\x1b[2K{} [{}{}] {:>3}% {}/{} ETA {}",
+            self.label, "#".repeat(filled), "-".repeat(BAR_WIDTH - filled), (fraction * 100.0) as u32, self.done, self.total, eta,
+        );
+        let _ = stderr.flush();
+        SHOWN.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        clear();
+    }
+}
+
+/// Clears a bar off the current line, if one is there
+pub fn clear() {
+    if SHOWN.swap(false, Ordering::Relaxed) {
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K");
+        let _ = stderr.flush();
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=9 => format!("{:.1}s", duration.as_secs_f64()),
+        10..=59 => format!("{}s", secs),
+        _ => format!("{}m{:02}s", secs / 60, secs % 60),
+    }
+}
@@ -0,0 +1,70 @@
+//! Reading answers back out of what a day prints, for anything running
+//! the day binaries rather than calling into them.
+
+/// Scrapes answers from a day's output, where each part is printed as
+/// a `Part N` header, a `----------` rule, and the answer as the last
+/// number before the next header
+pub fn parse_answers(stdout: &str) -> Vec<Option<String>> {
+    let mut answers: Vec<Option<String>> = vec!();
+    let mut part: Option<usize> = None;
+
+    for line in stdout.lines().map(|line| line.trim()) {
+        if let Some(n) = line.strip_prefix("Part ").and_then(|n| n.parse::<usize>().ok()) {
+            if answers.len() < n {
+                answers.resize(n, None);
+            }
+            part = Some(n);
+        } else if line.starts_with("Total execution time") {
+            part = None;
+        } else if let Some(n) = part {
+            if let Some(number) = last_number(line) {
+                answers[n - 1] = Some(number);
+            }
+        }
+    }
+    answers
+}
+
+/// Last integer in a line, ignoring terminal escape sequences
+fn last_number(line: &str) -> Option<String> {
+    let mut numbers: Vec<String> = vec!();
+    let mut prev = ' ';
+    for c in plain_text(line).chars() {
+        if c.is_ascii_digit() {
+            if !prev.is_ascii_digit() {
+                numbers.push(if prev == '-' { "-".to_string() } else { String::new() });
+            }
+            numbers.last_mut().unwrap().push(c);
+        }
+        prev = c;
+    }
+    numbers.pop()
+}
+
+/// A line of output with terminal escape sequences and carriage returns removed
+pub fn plain_text(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut in_escape = false;
+    for c in line.chars() {
+        if in_escape {
+            in_escape = !c.is_ascii_alphabetic();
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else if c != '\r' {
+            text.push(c);
+        }
+    }
+    text
+}
+
+/// Pulls the panic location and message out of stderr
+pub fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines().skip_while(|line| !line.contains("panicked at"));
+    let location = lines.next()?;
+    let location = &location[location.find("panicked at").unwrap()..];
+    match lines.next() {
+        // newer toolchains print the message on its own line
+        Some(msg) if location.ends_with(':') && !msg.starts_with("note:") => Some(format!("{} {}", location, msg)),
+        _ => Some(location.to_string()),
+    }
+}
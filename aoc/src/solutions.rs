@@ -0,0 +1,97 @@
+use std::env;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// Alternative implementations of a day's parts, all run over the same
+/// input, checked to agree, and timed side by side.
+///
+/// ```ignore
+/// Solutions::new()
+///     .part(1, "procedural", |depths: &[i32]| num_increases(depths))
+///     .part(1, "functional", |depths: &[i32]| depths.windows(2).filter(|w| w[1] > w[0]).count())
+///     .run(&depths);
+/// ```
+///
+/// Setting `AOC_BENCH=<runs>` (what `aoc bench` does) repeats every
+/// implementation that many times and reports the mean and fastest run.
+pub struct Solutions<T: ?Sized, R> {
+    implementations: Vec<Implementation<T, R>>,
+}
+
+struct Implementation<T: ?Sized, R> {
+    part: u8,
+    name: &'static str,
+    solve: Box<dyn Fn(&T) -> R>,
+}
+
+impl<T: ?Sized, R: PartialEq + Display> Default for Solutions<T, R> {
+    fn default() -> Self {
+        Solutions { implementations: vec!() }
+    }
+}
+
+impl<T: ?Sized, R: PartialEq + Display> Solutions<T, R> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an implementation of `part`, the first one registered is the reference
+    pub fn part(mut self, part: u8, name: &'static str, solve: impl Fn(&T) -> R + 'static) -> Self {
+        self.implementations.push(Implementation { part, name, solve: Box::new(solve) });
+        self
+    }
+
+    /// Runs every implementation and prints each part's timings and answer,
+    /// panicking when implementations of a part disagree
+    pub fn run(&self, input: &T) -> Vec<R> {
+        let runs = env::var("AOC_BENCH").ok().and_then(|runs| runs.parse::<u32>().ok()).unwrap_or(1).max(1);
+
+        let mut parts: Vec<u8> = self.implementations.iter().map(|imp| imp.part).collect();
+        parts.sort_unstable();
+        parts.dedup();
+
+        let mut answers: Vec<R> = vec!();
+        for part in parts {
+            println!("Part {}\r\n{}", part, "-".repeat(10));
+            let mut reference: Option<(&str, R)> = None;
+            for imp in self.implementations.iter().filter(|imp| imp.part == part) {
+                let (answer, timings) = time_runs(runs, || (imp.solve)(input));
+                print_timings(imp.name, &timings);
+
+                match &reference {
+                    None => reference = Some((imp.name, answer)),
+                    Some((name, expected)) if *expected != answer => {
+                        panic!("Part {} implementations disagree: {} = {}, {} = {}", part, name, expected, imp.name, answer)
+                    }
+                    Some(_) => (),
+                }
+            }
+
+            let (_, answer) = reference.unwrap();
+            println!("{}\r\n", answer);
+            answers.push(answer);
+        }
+        answers
+    }
+}
+
+fn time_runs<R>(runs: u32, mut solve: impl FnMut() -> R) -> (R, Vec<Duration>) {
+    let mut timings: Vec<Duration> = Vec::with_capacity(runs as usize);
+    let mut answer = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        answer = Some(solve());
+        timings.push(start.elapsed());
+    }
+    (answer.unwrap(), timings)
+}
+
+fn print_timings(name: &str, timings: &[Duration]) {
+    if timings.len() == 1 {
+        println!("{:<16}{:?}", name, timings[0]);
+    } else {
+        let mean = timings.iter().sum::<Duration>() / timings.len() as u32;
+        let min = timings.iter().min().unwrap();
+        println!("{:<16}mean {:<14}min {:<14}({} runs)", name, format!("{:?}", mean), format!("{:?}", min), timings.len());
+    }
+}
@@ -0,0 +1,163 @@
+//! Lightweight spans written out as Chrome trace-event JSON, viewable in
+//! `chrome://tracing` or <https://ui.perfetto.dev>.
+//!
+//! Spans are only recorded when the `trace` feature is enabled, otherwise
+//! [`span!`](crate::span) and [`init`] return empty guards, so solvers can be
+//! instrumented without paying for it.
+//!
+//! ```ignore
+//! let _trace = aoc::trace::init();     // writes trace.json when dropped
+//! let _span = aoc::span!("dijkstra");  // recorded until the end of scope
+//! ```
+
+/// Starts a span lasting until the returned guard is dropped, taking a
+/// static name or format arguments
+#[cfg(feature = "trace")]
+#[macro_export]
+macro_rules! span {
+    ($name:literal) => {
+        $crate::trace::Span::new(::std::borrow::Cow::Borrowed($name))
+    };
+    ($($arg:tt)+) => {
+        $crate::trace::Span::new(::std::borrow::Cow::Owned(format!($($arg)+)))
+    };
+}
+
+/// Starts a span lasting until the returned guard is dropped, taking a
+/// static name or format arguments
+#[cfg(not(feature = "trace"))]
+#[macro_export]
+macro_rules! span {
+    ($($arg:tt)+) => {{
+        // still type check the arguments, without formatting them
+        let _ = format_args!($($arg)+);
+        $crate::trace::Span
+    }};
+}
+
+#[cfg(feature = "trace")]
+pub use enabled::{init, Session, Span};
+
+#[cfg(not(feature = "trace"))]
+pub use disabled::{init, Session, Span};
+
+#[cfg(feature = "trace")]
+mod enabled {
+    use std::borrow::Cow;
+    use std::cell::Cell;
+    use std::env;
+    use std::fs;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Mutex, OnceLock};
+    use std::time::Instant;
+
+    /// A finished span, in microseconds since the trace started
+    struct Event {
+        name: Cow<'static, str>,
+        tid: u64,
+        ts: u128,
+        dur: u128,
+    }
+
+    static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    static NEXT_TID: AtomicU64 = AtomicU64::new(1);
+
+    thread_local! {
+        static TID: Cell<u64> = const { Cell::new(0) };
+    }
+
+    fn thread_id() -> u64 {
+        TID.with(|tid| {
+            if tid.get() == 0 {
+                tid.set(NEXT_TID.fetch_add(1, Ordering::Relaxed));
+            }
+            tid.get()
+        })
+    }
+
+    fn epoch() -> Instant {
+        *EPOCH.get_or_init(Instant::now)
+    }
+
+    /// Guard for an open span, created by [`span!`](crate::span)
+    pub struct Span {
+        name: Cow<'static, str>,
+        start: Instant,
+    }
+
+    impl Span {
+        pub fn new(name: Cow<'static, str>) -> Self {
+            epoch();
+            Span { name, start: Instant::now() }
+        }
+    }
+
+    impl Drop for Span {
+        fn drop(&mut self) {
+            let event = Event {
+                name: std::mem::take(&mut self.name),
+                tid: thread_id(),
+                ts: self.start.duration_since(epoch()).as_micros(),
+                dur: self.start.elapsed().as_micros(),
+            };
+            EVENTS.lock().unwrap().push(event);
+        }
+    }
+
+    /// Writes the recorded spans to `$AOC_TRACE`, or `trace.json`, when dropped
+    pub struct Session {
+        path: String,
+    }
+
+    /// Starts tracing for the rest of `main`
+    pub fn init() -> Session {
+        epoch();
+        Session { path: env::var("AOC_TRACE").unwrap_or_else(|_| "trace.json".to_string()) }
+    }
+
+    impl Drop for Session {
+        fn drop(&mut self) {
+            let events = EVENTS.lock().unwrap();
+            let mut json = String::from("{\"traceEvents\":[\n");
+            for (i, event) in events.iter().enumerate() {
+                json.push_str(&format!(
+                    "{{\"name\":\"{}\",\"cat\":\"aoc\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":{}}}{}\n",
+                    escape(&event.name), event.ts, event.dur, event.tid,
+                    if i + 1 < events.len() { "," } else { "" }
+                ));
+            }
+            json.push_str("],\"displayTimeUnit\":\"ms\"}\n");
+
+            match fs::write(&self.path, json) {
+                Ok(()) => eprintln!("Wrote {} spans to {}", events.len(), self.path),
+                Err(e) => eprintln!("Unable to write trace {}: {}", self.path, e),
+            }
+        }
+    }
+
+    fn escape(name: &str) -> String {
+        name.chars().fold(String::with_capacity(name.len()), |mut acc, c| {
+            match c {
+                '"' => acc.push_str("\\\""),
+                '\\' => acc.push_str("\\\\"),
+                c if c.is_control() => acc.push_str(&format!("\\u{:04x}", c as u32)),
+                c => acc.push(c),
+            }
+            acc
+        })
+    }
+}
+
+#[cfg(not(feature = "trace"))]
+mod disabled {
+    /// Tracing is compiled out, nothing to write
+    pub struct Session;
+
+    /// Tracing is compiled out, nothing to record
+    pub struct Span;
+
+    pub fn init() -> Session {
+        Session
+    }
+}
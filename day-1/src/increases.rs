@@ -0,0 +1,108 @@
+use std::collections::VecDeque;
+
+/// Adds [`window_increases`](Increases::window_increases) to every iterator
+pub trait Increases: Iterator + Sized {
+    /// Whether each sum of `window` consecutive items is larger than the sum
+    /// before it, one `bool` per window after the first. Only the last
+    /// `window` items are kept, so this works over unbounded streams.
+    ///
+    /// The sums are never added up: items must be numbers whose sums order
+    /// like ordinary addition, as integers do, not saturating or wrapping.
+    fn window_increases(self, window: usize) -> WindowIncreases<Self> {
+        WindowIncreases { items: self, window: Window::new(window) }
+    }
+}
+
+impl<I: Iterator> Increases for I {}
+
+pub struct WindowIncreases<I: Iterator> {
+    items: I,
+    window: Window<I::Item>,
+}
+
+impl<I, T> Iterator for WindowIncreases<I>
+where
+    I: Iterator<Item = T>,
+    T: Ord,
+{
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        loop {
+            if let Some(increase) = self.window.push(self.items.next()?) {
+                return Some(increase);
+            }
+        }
+    }
+}
+
+/// The sliding window behind [`WindowIncreases`], for items pushed in as
+/// they arrive rather than pulled from an iterator
+pub struct Window<T> {
+    size: usize,
+    recent: VecDeque<T>,
+    pub increases: usize,
+}
+
+impl<T> Window<T> {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "windows need at least one item");
+        Window { size, recent: VecDeque::with_capacity(size + 1), increases: 0 }
+    }
+}
+
+impl<T: Ord> Window<T> {
+    /// Whether the window ending at `item` sums to more than the one before,
+    /// `None` until there are two windows to compare
+    pub fn push(&mut self, item: T) -> Option<bool> {
+        if self.recent.len() < self.size {
+            self.recent.push_back(item);
+            return None;
+        }
+        // neighbouring windows share all but their ends, so the later sum is
+        // larger exactly when the item entering beats the one leaving
+        let leaving = self.recent.pop_front()?;
+        let increase = item > leaving;
+        self.recent.push_back(item);
+        self.increases += increase as usize;
+        Some(increase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_from_a_first_depth_of_zero() {
+        let increases: Vec<bool> = [0, 1, 1, 0, 5].into_iter().window_increases(1).collect();
+        assert_eq!(increases, vec![true, false, false, true]);
+    }
+
+    #[test]
+    fn counts_negative_readings() {
+        let depths = [-5, -3, -10, -10, -1, -2];
+        assert_eq!(depths.into_iter().window_increases(1).filter(|&increase| increase).count(), 2);
+        // sums -18, -23, -21, -13
+        let increases: Vec<bool> = depths.into_iter().window_increases(3).collect();
+        assert_eq!(increases, vec![false, true, true]);
+    }
+
+    #[test]
+    fn matches_the_example() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(depths.into_iter().window_increases(1).filter(|&increase| increase).count(), 7);
+        assert_eq!(depths.into_iter().window_increases(3).filter(|&increase| increase).count(), 5);
+    }
+
+    #[test]
+    fn push_waits_for_two_windows() {
+        let mut window = Window::new(2);
+        assert_eq!(window.push(0), None);
+        assert_eq!(window.push(-4), None);
+        assert_eq!(window.push(1), Some(true));
+        assert_eq!(window.push(-4), Some(false));
+        assert_eq!(window.push(-4), Some(false));
+        assert_eq!(window.increases, 1);
+    }
+}
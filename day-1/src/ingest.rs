@@ -0,0 +1,263 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// What to do with readings that are missing, garbled or out of range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    Skip,
+    Interpolate,
+    Fail,
+}
+
+impl FromStr for Policy {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, String> {
+        match name {
+            "skip" => Ok(Policy::Skip),
+            "interpolate" => Ok(Policy::Interpolate),
+            "fail" => Ok(Policy::Fail),
+            _ => Err(format!("unknown policy {:?}, expected skip, interpolate or fail", name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    Missing,
+    Garbled(String),
+    OutOfRange(i32),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Missing => write!(f, "missing reading"),
+            Problem::Garbled(text) => write!(f, "garbled reading {:?}", text),
+            Problem::OutOfRange(depth) => write!(f, "out of range reading {}", depth),
+        }
+    }
+}
+
+/// A bad line and the depth put in its place, if any
+pub struct Repair {
+    pub line: usize,
+    pub problem: Problem,
+    pub depth: Option<i32>,
+}
+
+pub struct Ingested {
+    pub depths: Vec<i32>,
+    pub lines: usize,
+    /// Lines with a timestamp in their second column
+    pub timestamped: usize,
+    /// Lines whose timestamp isn't a number, and the timestamp
+    pub unreadable_timestamps: Vec<(usize, String)>,
+    /// Lines timestamped earlier than the line before, when interpolating
+    /// over time
+    pub backwards: Vec<usize>,
+    pub repairs: Vec<Repair>,
+}
+
+impl Ingested {
+    /// One line count of what went wrong and how it was handled
+    pub fn summary(&self, policy: Policy) -> String {
+        let count = |matches: fn(&Problem) -> bool| self.repairs.iter().filter(|repair| matches(&repair.problem)).count();
+        let action = match policy {
+            Policy::Skip => "skipped",
+            _ => "interpolated",
+        };
+        let mut summary = format!(
+            "{} {} of {} readings: {} missing, {} garbled, {} out of range",
+            action, self.repairs.len(), self.lines,
+            count(|problem| *problem == Problem::Missing),
+            count(|problem| matches!(problem, Problem::Garbled(_))),
+            count(|problem| matches!(problem, Problem::OutOfRange(_))),
+        );
+        if !self.unreadable_timestamps.is_empty() {
+            summary.push_str(&format!(", {} unreadable timestamps", self.unreadable_timestamps.len()));
+            if policy == Policy::Interpolate {
+                summary.push_str(", so interpolated by line");
+            }
+        }
+        summary
+    }
+}
+
+/// Reads one depth per line, optionally followed by a timestamp after a
+/// comma or whitespace. Blank lines, an empty depth column or a lone `-`
+/// are missing readings, and depths outside `min..=max` are out of range.
+pub fn ingest(text: &str, policy: Policy, min: Option<i32>, max: Option<i32>) -> Result<Ingested, String> {
+    let mut readings: Vec<Result<i32, Problem>> = vec!();
+    let mut timestamps: Vec<Option<f64>> = vec!();
+    let mut unreadable_timestamps: Vec<(usize, String)> = vec!();
+    // trailing blank lines are the end of the file rather than missing readings
+    for line in text.trim_end().lines() {
+        let mut columns: Vec<&str> = match line.contains(',') {
+            true => line.split(',').map(|column| column.trim()).collect(),
+            false => line.split_whitespace().collect(),
+        };
+        columns.resize(2, "");
+        timestamps.push(match columns[1] {
+            "" => None,
+            stamp => Some(stamp.parse::<f64>().ok().filter(|stamp| stamp.is_finite()).unwrap_or_else(|| {
+                unreadable_timestamps.push((timestamps.len() + 1, stamp.to_string()));
+                f64::NAN
+            })),
+        });
+        readings.push(reading(columns[0], min, max));
+    }
+
+    let timestamped = timestamps.iter().filter(|stamp| stamp.is_some()).count();
+    // interpolate over time when every line has a numeric timestamp, otherwise over lines
+    let by_time = timestamps.iter().all(|stamp| stamp.is_some_and(|stamp| !stamp.is_nan()));
+    let positions: Vec<f64> = match by_time {
+        true => timestamps.iter().map(|stamp| stamp.unwrap()).collect(),
+        false => (0..readings.len()).map(|idx| idx as f64).collect(),
+    };
+    let backwards = match by_time {
+        true => (1..positions.len()).filter(|&idx| positions[idx] < positions[idx - 1]).map(|idx| idx + 1).collect(),
+        false => vec!(),
+    };
+
+    let good: Vec<usize> = (0..readings.len()).filter(|&idx| readings[idx].is_ok()).collect();
+    let mut ingested = Ingested { depths: vec!(), lines: readings.len(), timestamped, unreadable_timestamps, backwards, repairs: vec!() };
+    for (idx, reading) in readings.iter().enumerate() {
+        match reading {
+            Ok(depth) => ingested.depths.push(*depth),
+            Err(problem) => {
+                let depth = match policy {
+                    Policy::Fail => return Err(format!("line {}: {}", idx + 1, problem)),
+                    Policy::Skip => None,
+                    Policy::Interpolate => Some(interpolate(&readings, &positions, &good, idx).ok_or("no good readings to interpolate from")?),
+                };
+                ingested.depths.extend(depth);
+                ingested.repairs.push(Repair { line: idx + 1, problem: problem.clone(), depth });
+            }
+        }
+    }
+    Ok(ingested)
+}
+
+/// One depth column, which is missing when empty or a lone `-` and out of
+/// range outside `min..=max`
+pub fn reading(depth: &str, min: Option<i32>, max: Option<i32>) -> Result<i32, Problem> {
+    match depth {
+        "" | "-" => Err(Problem::Missing),
+        depth => match depth.parse::<i32>() {
+            Ok(depth) if min.is_some_and(|min| depth < min) || max.is_some_and(|max| depth > max) => Err(Problem::OutOfRange(depth)),
+            Ok(depth) => Ok(depth),
+            Err(_) => Err(Problem::Garbled(depth.to_string())),
+        },
+    }
+}
+
+/// The depth on the line between the nearest good readings either side of
+/// `idx`, or the nearest one at either end of the sweep
+fn interpolate(readings: &[Result<i32, Problem>], positions: &[f64], good: &[usize], idx: usize) -> Option<i32> {
+    let after = good.partition_point(|&good_idx| good_idx < idx);
+    let depth = |good_idx: usize| *readings[good_idx].as_ref().unwrap() as f64;
+    match (after.checked_sub(1).map(|before| good[before]), good.get(after).copied()) {
+        (Some(before), Some(after)) => {
+            let span = positions[after] - positions[before];
+            let fraction = if span == 0.0 { 0.5 } else { (positions[idx] - positions[before]) / span };
+            Some((depth(before) + (depth(after) - depth(before)) * fraction).round() as i32)
+        }
+        (Some(nearest), None) | (None, Some(nearest)) => Some(depth(nearest) as i32),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depths(text: &str, policy: Policy) -> Vec<i32> {
+        ingest(text, policy, None, None).unwrap().depths
+    }
+
+    fn repairs(ingested: &Ingested) -> Vec<(usize, Problem, Option<i32>)> {
+        ingested.repairs.iter().map(|repair| (repair.line, repair.problem.clone(), repair.depth)).collect()
+    }
+
+    #[test]
+    fn interpolate_between_two_readings() {
+        let ingested = ingest("100\n-\n\n130\n", Policy::Interpolate, None, None).unwrap();
+        assert_eq!(ingested.depths, vec!(100, 110, 120, 130));
+        assert_eq!(repairs(&ingested), vec!((2, Problem::Missing, Some(110)), (3, Problem::Missing, Some(120))));
+    }
+
+    #[test]
+    fn interpolate_at_either_end() {
+        let ingested = ingest("-\nabc\n100\n110\n5000", Policy::Interpolate, None, Some(1000)).unwrap();
+        assert_eq!(ingested.depths, vec!(100, 100, 100, 110, 110));
+        assert_eq!(
+            repairs(&ingested),
+            vec!(
+                (1, Problem::Missing, Some(100)),
+                (2, Problem::Garbled("abc".to_string()), Some(100)),
+                (5, Problem::OutOfRange(5000), Some(110)),
+            )
+        );
+    }
+
+    #[test]
+    fn nothing_to_interpolate_from() {
+        assert_eq!(ingest("-\nabc\n", Policy::Interpolate, None, None).err().unwrap(), "no good readings to interpolate from");
+        assert_eq!(depths("-\nabc\n", Policy::Skip), vec!());
+    }
+
+    #[test]
+    fn interpolate_over_time_or_lines() {
+        assert_eq!(depths("100,0\n-,1\n200,4", Policy::Interpolate), vec!(100, 125, 200));
+        assert_eq!(depths("100 0\n- 1\n200 4", Policy::Interpolate), vec!(100, 125, 200));
+        // one line without a timestamp means falling back to line numbers
+        assert_eq!(depths("100,0\n-,1\n200", Policy::Interpolate), vec!(100, 150, 200));
+        assert_eq!(depths("100,0\n-,soon\n200,4", Policy::Interpolate), vec!(100, 150, 200));
+    }
+
+    #[test]
+    fn interpolate_over_no_time() {
+        assert_eq!(depths("100,5\n-,5\n201,5", Policy::Interpolate), vec!(100, 151, 201));
+    }
+
+    #[test]
+    fn timestamps() {
+        let ingested = ingest("100,0\n101,2\n102,1\n103,3\n104,0", Policy::Skip, None, None).unwrap();
+        assert_eq!((ingested.timestamped, &ingested.backwards), (5, &vec!(3, 5)));
+
+        let ingested = ingest("100,0\n101,noon\n102\n103,inf\n104,1", Policy::Skip, None, None).unwrap();
+        assert_eq!(ingested.timestamped, 4);
+        assert_eq!(ingested.unreadable_timestamps, vec!((2, "noon".to_string()), (4, "inf".to_string())));
+        // without a time for every line there's no order to go backwards in
+        assert_eq!(ingested.backwards, vec!());
+        assert_eq!(
+            ingested.summary(Policy::Interpolate),
+            "interpolated 0 of 5 readings: 0 missing, 0 garbled, 0 out of range, 2 unreadable timestamps, so interpolated by line"
+        );
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_dropped() {
+        let ingested = ingest("100\n\n101\n\n\n", Policy::Skip, None, None).unwrap();
+        assert_eq!((&ingested.depths, ingested.lines), (&vec!(100, 101), 3));
+        assert_eq!(repairs(&ingested), vec!((2, Problem::Missing, None)));
+        assert_eq!(ingested.summary(Policy::Skip), "skipped 1 of 3 readings: 1 missing, 0 garbled, 0 out of range");
+    }
+
+    #[test]
+    fn fail_on_the_first_bad_line() {
+        assert_eq!(ingest("100\n101\n5000\nabc\n", Policy::Fail, Some(0), Some(1000)).err().unwrap(), "line 3: out of range reading 5000");
+        assert_eq!(ingest("100\n1o1\n\n", Policy::Fail, None, None).err().unwrap(), "line 2: garbled reading \"1o1\"");
+        assert_eq!(ingest("100\n\n101", Policy::Fail, None, None).err().unwrap(), "line 2: missing reading");
+        assert_eq!(depths("100\n101\n\n", Policy::Fail), vec!(100, 101));
+    }
+
+    #[test]
+    fn readings() {
+        assert_eq!(reading("-", None, None), Err(Problem::Missing));
+        assert_eq!(reading("-5", Some(0), None), Err(Problem::OutOfRange(-5)));
+        assert_eq!(reading("0", Some(0), Some(0)), Ok(0));
+        assert_eq!(reading("2147483648", None, None), Err(Problem::Garbled("2147483648".to_string())));
+    }
+}
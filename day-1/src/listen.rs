@@ -0,0 +1,217 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+use crate::increases::Window;
+use crate::ingest::{self, Policy};
+
+/// Running part 1 and part 2 counts over one connection's depths
+struct Sonar {
+    depths: usize,
+    /// Depths counted at the last print
+    shown: usize,
+    single: Window<i32>,
+    summed: Window<i32>,
+}
+
+impl Sonar {
+    fn new() -> Self {
+        Sonar { depths: 0, shown: 0, single: Window::new(1), summed: Window::new(3) }
+    }
+
+    fn push(&mut self, depth: i32) {
+        self.depths += 1;
+        self.single.push(depth);
+        self.summed.push(depth);
+    }
+
+    fn print(&mut self, out: &mut impl Write) {
+        if self.shown == self.depths {
+            return;
+        }
+        self.shown = self.depths;
+        let _ = writeln!(out, "depths {:<8} part 1 {:<8} part 2 {}", self.depths, self.single.increases, self.summed.increases);
+        let _ = out.flush();
+    }
+}
+
+/// What counts as a good reading on a connection, and what to do with the
+/// rest. Only `skip` and `fail` apply, a stream can't wait for the reading
+/// after a bad one to interpolate it.
+#[derive(Clone, Copy)]
+pub struct Readings {
+    pub policy: Policy,
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+/// Listens on `address`, a TCP `host:port` or a Unix socket path (given as
+/// `unix:<path>` or anything with a `/`), taking one connection at a time
+/// and printing the counters whenever a batch of depths has been read
+pub fn listen(address: &str, readings: Readings) -> io::Result<()> {
+    match unix_path(address) {
+        Some(path) => listen_unix(path, readings),
+        None => {
+            let listener = TcpListener::bind(address)?;
+            aoc::info!("listening on {}", listener.local_addr()?);
+            for stream in listener.incoming() {
+                // a failed accept or a peer already gone shouldn't end the server
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        aoc::warn!("accepting a connection: {}", e);
+                        continue;
+                    }
+                };
+                match stream.peer_addr() {
+                    Ok(peer) => aoc::info!("connection from {}", peer),
+                    Err(e) => {
+                        aoc::warn!("connection lost before it started: {}", e);
+                        continue;
+                    }
+                }
+                serve(stream, &mut io::stdout(), readings);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn unix_path(address: &str) -> Option<&str> {
+    match address.strip_prefix("unix:") {
+        Some(path) => Some(path),
+        None if address.contains('/') => Some(address),
+        None => None,
+    }
+}
+
+#[cfg(unix)]
+fn listen_unix(path: &str, readings: Readings) -> io::Result<()> {
+    use std::fs;
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+
+    // a socket left behind by an earlier run would stop us binding
+    if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    aoc::info!("listening on {}", path);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                aoc::info!("connection on {}", path);
+                serve(stream, &mut io::stdout(), readings);
+            }
+            Err(e) => aoc::warn!("accepting a connection: {}", e),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn listen_unix(path: &str, _readings: Readings) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, format!("can't listen on {}, Unix sockets need a Unix", path)))
+}
+
+/// Counts one connection's newline delimited depths until it closes,
+/// writing the counters to `out`. Bad lines are skipped with a warning, or
+/// end the connection under `fail`.
+fn serve(stream: impl Read, out: &mut impl Write, readings: Readings) {
+    let mut reader = BufReader::new(stream);
+    let mut sonar = Sonar::new();
+    let mut line = String::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => (),
+            Err(e) => {
+                aoc::warn!("connection lost: {}", e);
+                break;
+            }
+        }
+        number += 1;
+        match line.trim() {
+            "" => (),
+            reading => match ingest::reading(reading, readings.min, readings.max) {
+                Ok(depth) => sonar.push(depth),
+                Err(problem) if readings.policy == Policy::Fail => {
+                    aoc::error!("line {}: {}, closing the connection", number, problem);
+                    break;
+                }
+                Err(problem) => aoc::warn!("line {}: {}, skipped", number, problem),
+            },
+        }
+        // print once everything that has arrived so far is counted
+        if reader.buffer().is_empty() {
+            sonar.print(out);
+        }
+    }
+    sonar.print(out);
+    aoc::info!("connection closed after {} depths", sonar.depths);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::io::Cursor;
+    use std::net::TcpStream;
+    use std::thread;
+
+    const SKIP: Readings = Readings { policy: Policy::Skip, min: Some(0), max: Some(10000) };
+    const FAIL: Readings = Readings { policy: Policy::Fail, min: Some(0), max: Some(10000) };
+
+    /// Hands out one chunk per read, like packets arriving on a socket
+    struct Chunks(VecDeque<&'static str>);
+
+    impl Read for Chunks {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some(chunk) = self.0.pop_front() else { return Ok(0) };
+            buf[..chunk.len()].copy_from_slice(chunk.as_bytes());
+            Ok(chunk.len())
+        }
+    }
+
+    fn counts(depths: usize, part1: usize, part2: usize) -> String {
+        format!("depths {:<8} part 1 {:<8} part 2 {}\n", depths, part1, part2)
+    }
+
+    fn served(stream: impl Read, readings: Readings) -> String {
+        let mut out = vec!();
+        serve(stream, &mut out, readings);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn counts_each_batch() {
+        let chunks = Chunks(VecDeque::from(["199\n200\n208\n", "210\n200\n207\n240\n2", "69\n260\n", "263\n"]));
+        // the depth split across chunks holds back a print until its line is done
+        assert_eq!(served(chunks, SKIP), counts(3, 2, 0) + &counts(9, 6, 4) + &counts(10, 7, 5));
+        assert_eq!(served(Cursor::new("199\n200\n208\n210\n200\n207\n240\n269\n260\n263"), SKIP), counts(10, 7, 5));
+        assert_eq!(served(Cursor::new(""), SKIP), "");
+    }
+
+    #[test]
+    fn bad_lines() {
+        let text = "199\nabc\n\n200\n99999\n-5\n208\n";
+        assert_eq!(served(Cursor::new(text), SKIP), counts(3, 2, 0));
+        assert_eq!(served(Cursor::new(text), FAIL), counts(1, 0, 0));
+        assert_eq!(served(Cursor::new("199\n\n \n200\n"), FAIL), counts(2, 1, 0));
+    }
+
+    #[test]
+    fn serves_a_socket() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(b"199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n").unwrap();
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let out = served(stream, SKIP);
+        client.join().unwrap();
+        assert!(out.ends_with(&counts(10, 7, 5)), "{}", out);
+    }
+}
@@ -1,55 +1,139 @@
-use std::io::{self, BufRead};
+use std::process;
+use aoc::input::Input;
+use aoc::solutions::Solutions;
+use structopt::StructOpt;
 
-fn main() {
-    // Read from stdin to vec
-    let stdin = io::stdin();
-    let mut depths: Vec<i32> = Vec::new();
-    for line in stdin.lock().lines() {
-        depths.push(line.unwrap().parse::<i32>().unwrap());
-    }
-    
-    // Procedureal
-    // part 1
-    println!("Part 1\r\n{}", "-".repeat(10));
-    println!("{}\r\n", num_increases(&depths));
-    
-    // part 2
-    println!("Part 2\r\n{}", "-".repeat(10));
-    let summed_depths = summed_sliding_values(&depths, 3);
-    println!("{}\r\n", num_increases(&summed_depths));
-
-    // Functional versions
-    // part 1
-    println!("Functional Part 1\r\n{}", "-".repeat(10));
-    println!("{}\r\n", depths.windows(2).map(|value| { value[1] > value[0] } ).filter(|&increase| increase).count());
-
-    // part 1
-    println!("Functional Part 2\r\n{}", "-".repeat(10));
-    println!("{}", depths.windows(3).map(|sample| { sample.iter().fold(0,|acc,x| { acc + x})}).collect::<Vec<i32>>()
-                         .windows(2).map(|value| { value[1] > value[0] } ).filter(|&increase| increase).count());
+mod increases;
+mod ingest;
+mod listen;
+mod report;
+
+use increases::Increases;
+use ingest::Policy;
+use report::Report;
 
+// Commandline arguments
+#[derive(StructOpt)]
+struct Cli {
+    /// Print an analytics report of the depths instead of the answers
+    #[structopt(long)]
+    report: bool,
+    /// Print the report as JSON
+    #[structopt(long)]
+    json: bool,
+    /// Changes between readings this many standard deviations from the mean are spikes
+    #[structopt(long, default_value = "3.0", parse(try_from_str = z_score))]
+    z_score: f64,
+    /// Metres in each histogram band
+    #[structopt(long, default_value = "100", parse(try_from_str = bucket))]
+    bucket: i32,
+    /// What to do with missing, garbled or out of range readings: skip, interpolate or fail
+    #[structopt(long, default_value = "fail")]
+    policy: Policy,
+    /// Shallower readings are out of range
+    #[structopt(long, allow_hyphen_values = true)]
+    min_depth: Option<i32>,
+    /// Deeper readings are out of range
+    #[structopt(long, allow_hyphen_values = true)]
+    max_depth: Option<i32>,
+    /// Count depths arriving on a TCP address or Unix socket instead of stdin
+    #[structopt(long)]
+    listen: Option<String>,
+    /// Show each repaired reading
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: u8,
+    /// Only show errors
+    #[structopt(short, long)]
+    quiet: bool,
 }
 
-fn num_increases(depths: &Vec<i32> ) -> i32 {
-    let mut num_increases: i32 = 0;
-    let mut prev_depth: i32 = -1;
-    
-    for cur_depth in depths {
-        if prev_depth > 0 && *cur_depth > prev_depth {
-            num_increases += 1;
+fn main() {
+    let args = Cli::from_args();
+    aoc::log::set_verbosity(args.verbose, args.quiet);
+
+    if let Some(address) = args.listen {
+        if args.policy == Policy::Interpolate {
+            aoc::error!("--policy interpolate needs the readings after a bad one, use skip or fail with --listen");
+            process::exit(1);
+        }
+        let readings = listen::Readings { policy: args.policy, min: args.min_depth, max: args.max_depth };
+        if let Err(e) = listen::listen(&address, readings) {
+            aoc::error!("{}: {}", address, e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Read from stdin to vec
+    let input = Input::from_stdin();
+    let ingested = match ingest::ingest(input.text(), args.policy, args.min_depth, args.max_depth) {
+        Ok(ingested) => ingested,
+        Err(e) => {
+            aoc::error!("{}", e);
+            process::exit(1);
         }
-        prev_depth = *cur_depth;
+    };
+    for repair in ingested.repairs.iter() {
+        match repair.depth {
+            Some(depth) => aoc::info!("line {}: {}, interpolated as {}", repair.line, repair.problem, depth),
+            None => aoc::info!("line {}: {}, skipped", repair.line, repair.problem),
+        }
+    }
+    for (line, stamp) in ingested.unreadable_timestamps.iter() {
+        aoc::info!("line {}: unreadable timestamp {:?}", line, stamp);
+    }
+    if !ingested.repairs.is_empty() || !ingested.unreadable_timestamps.is_empty() {
+        aoc::warn!("{}", ingested.summary(args.policy));
+    }
+    if let Some(line) = ingested.backwards.first().filter(|_| !ingested.repairs.is_empty() && args.policy == Policy::Interpolate) {
+        let more = match ingested.backwards.len() {
+            1 => String::new(),
+            n => format!(" (and {} more)", n - 1),
+        };
+        aoc::warn!("line {}: timestamp earlier than the line before{}, interpolating around it extrapolates", line, more);
+    }
+    if ingested.timestamped > 0 {
+        aoc::info!("{} of {} readings have timestamps", ingested.timestamped, ingested.lines);
     }
+    let depths = ingested.depths;
 
-    return num_increases;
+    if args.report || args.json {
+        let mut report = Report::new(args.z_score, args.bucket);
+        depths.iter().for_each(|&depth| report.push(depth));
+        let report = report.finish();
+        print!("{}", if args.json { report.json() + "\n" } else { report.table() });
+        return;
+    }
+
+    Solutions::new()
+        // Streaming, holding only the current windows
+        .part(1, "streaming", |depths: &Vec<i32>| num_increases(depths.iter().copied(), 1))
+        .part(2, "streaming", |depths: &Vec<i32>| num_increases(depths.iter().copied(), 3))
+        // Functional versions
+        .part(1, "functional", |depths: &Vec<i32>| {
+            depths.windows(2).map(|value| { value[1] > value[0] } ).filter(|&increase| increase).count()
+        })
+        .part(2, "functional", |depths: &Vec<i32>| {
+            depths.windows(3).map(|sample| { sample.iter().fold(0,|acc,x| { acc + x})}).collect::<Vec<i32>>()
+                  .windows(2).map(|value| { value[1] > value[0] } ).filter(|&increase| increase).count()
+        })
+        .run(&depths);
 }
 
-fn summed_sliding_values(depths: &Vec<i32>, window: usize) -> Vec<i32> {
-    let mut summed_depths:  Vec<i32> = Vec::new();
+fn z_score(text: &str) -> Result<f64, String> {
+    match text.parse::<f64>() {
+        Ok(z_score) if z_score.is_finite() && z_score > 0.0 => Ok(z_score),
+        _ => Err(format!("{} isn't a positive number of standard deviations", text)),
+    }
+}
 
-    for sample in depths.windows(window) {
-        summed_depths.push(sample.iter().sum());
+fn bucket(text: &str) -> Result<i32, String> {
+    match text.parse::<i32>() {
+        Ok(bucket) if bucket > 0 => Ok(bucket),
+        _ => Err(format!("{} isn't a positive number of metres", text)),
     }
+}
 
-    return summed_depths;
+fn num_increases(depths: impl Iterator<Item = i32>, window: usize) -> usize {
+    depths.window_increases(window).filter(|&increase| increase).count()
 }
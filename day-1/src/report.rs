@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+/// A jump in depth unusually large next to the jumps before it
+pub struct Spike {
+    pub index: usize,
+    pub depth: i32,
+    pub change: i64,
+    pub z_score: f64,
+}
+
+/// Statistics over a sonar sweep, gathered one depth at a time so the
+/// readings never need to be held
+pub struct Report {
+    z_threshold: f64,
+    bucket: i64,
+    pub readings: usize,
+    pub shallowest: Option<i32>,
+    pub deepest: Option<i32>,
+    pub increases: usize,
+    /// Start index and length of the longest strictly increasing run
+    pub longest_run: (usize, usize),
+    /// Index of the reading and how much deeper it was than the one before
+    pub steepest_descent: Option<(usize, i64)>,
+    /// How many times each plateau length (readings in a row at one depth) was seen
+    pub plateaus: BTreeMap<usize, usize>,
+    /// Readings counted by the start of their `bucket` metres deep band,
+    /// in i64 as the bands around the extremes of i32 don't fit in one
+    pub histogram: BTreeMap<i64, usize>,
+    pub spikes: Vec<Spike>,
+    previous: Option<i32>,
+    run_start: usize,
+    plateau: usize,
+    // running mean and variance of the changes between readings (Welford)
+    changes: usize,
+    mean_change: f64,
+    change_m2: f64,
+}
+
+impl Report {
+    /// Spikes are changes more than `z_threshold` standard deviations from
+    /// the mean change so far, the histogram has `bucket` metre bands
+    pub fn new(z_threshold: f64, bucket: i32) -> Self {
+        assert!(bucket > 0, "histogram buckets need a width");
+        Report {
+            z_threshold, bucket: bucket.into(),
+            readings: 0, shallowest: None, deepest: None, increases: 0,
+            longest_run: (0, 0), steepest_descent: None,
+            plateaus: BTreeMap::new(), histogram: BTreeMap::new(), spikes: vec!(),
+            previous: None, run_start: 0, plateau: 1,
+            changes: 0, mean_change: 0.0, change_m2: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, depth: i32) {
+        let index = self.readings;
+        self.readings += 1;
+        self.shallowest = Some(self.shallowest.map_or(depth, |shallowest| shallowest.min(depth)));
+        self.deepest = Some(self.deepest.map_or(depth, |deepest| deepest.max(depth)));
+        *self.histogram.entry(i64::from(depth).div_euclid(self.bucket) * self.bucket).or_insert(0) += 1;
+
+        if let Some(previous) = self.previous {
+            // changes between i32 depths can be twice as large as one
+            let change = i64::from(depth) - i64::from(previous);
+            if change > 0 {
+                self.increases += 1;
+            } else {
+                self.run_start = index;
+            }
+            if change > self.steepest_descent.map_or(0, |(_, steepest)| steepest) {
+                self.steepest_descent = Some((index, change));
+            }
+            if change == 0 {
+                self.plateau += 1;
+            } else {
+                self.end_plateau();
+            }
+            self.spot_spike(index, depth, change);
+        }
+        if index + 1 - self.run_start > self.longest_run.1 {
+            self.longest_run = (self.run_start, index + 1 - self.run_start);
+        }
+        self.previous = Some(depth);
+    }
+
+    fn end_plateau(&mut self) {
+        if self.plateau > 1 {
+            *self.plateaus.entry(self.plateau).or_insert(0) += 1;
+        }
+        self.plateau = 1;
+    }
+
+    /// Scores the change against those before it, then counts it in
+    fn spot_spike(&mut self, index: usize, depth: i32, change: i64) {
+        let value = change as f64;
+        if self.changes >= 2 {
+            let deviation = (self.change_m2 / (self.changes - 1) as f64).sqrt();
+            if deviation > 0.0 {
+                let z_score = (value - self.mean_change) / deviation;
+                if z_score.abs() > self.z_threshold {
+                    self.spikes.push(Spike { index, depth, change, z_score });
+                }
+            }
+        }
+        self.changes += 1;
+        let delta = value - self.mean_change;
+        self.mean_change += delta / self.changes as f64;
+        self.change_m2 += delta * (value - self.mean_change);
+    }
+
+    /// Closes off a plateau still running at the last reading
+    pub fn finish(mut self) -> Self {
+        self.end_plateau();
+        self
+    }
+
+    pub fn table(&self) -> String {
+        let mut text = String::new();
+        let mut row = |name: &str, value: String| text.push_str(&format!("{:<20}{}\n", name, value));
+        row("Readings", self.readings.to_string());
+        row("Shallowest", self.shallowest.map_or("-".to_string(), |depth| depth.to_string()));
+        row("Deepest", self.deepest.map_or("-".to_string(), |depth| depth.to_string()));
+        row("Increases", self.increases.to_string());
+        row("Longest run", format!("{} readings from #{}", self.longest_run.1, self.longest_run.0));
+        row("Steepest descent", self.steepest_descent.map_or("-".to_string(), |(index, change)| format!("+{} at #{}", change, index)));
+        row("Plateaus", match self.plateaus.is_empty() {
+            true => "none".to_string(),
+            false => self.plateaus.iter().map(|(length, count)| format!("{}x{}", count, length)).collect::<Vec<String>>().join(", "),
+        });
+
+        let bands: Vec<String> = self.histogram.keys().map(|start| format!("{}..{}", start, start + self.bucket)).collect();
+        let width = bands.iter().map(|band| band.len() + 2).max().unwrap_or(0).max(20);
+        text.push_str(&format!("\n{:<width$}Readings\n{}\n", "Depth", "-".repeat(width + 10), width = width));
+        let most = self.histogram.values().max().copied().unwrap_or(1);
+        for (band, count) in bands.iter().zip(self.histogram.values()) {
+            text.push_str(&format!("{:<width$}{:<7}{}\n", band, count, "#".repeat((count * 40).div_ceil(most)), width = width));
+        }
+
+        text.push_str(&format!("\n{:<10}{:<10}{:<10}z > {}\n{}\n", "Spike", "Depth", "Change", self.z_threshold, "-".repeat(40)));
+        for spike in self.spikes.iter() {
+            text.push_str(&format!("#{:<9}{:<10}{:<+10}{:.2}\n", spike.index, spike.depth, spike.change, spike.z_score));
+        }
+        if self.spikes.is_empty() {
+            text.push_str("none\n");
+        }
+        text
+    }
+
+    pub fn json(&self) -> String {
+        let optional = |value: Option<i32>| value.map_or("null".to_string(), |value| value.to_string());
+        let steepest = match self.steepest_descent {
+            Some((index, change)) => format!("{{\"index\":{},\"change\":{}}}", index, change),
+            None => "null".to_string(),
+        };
+        let plateaus: Vec<String> = self.plateaus.iter().map(|(length, count)| format!("{{\"length\":{},\"count\":{}}}", length, count)).collect();
+        let histogram: Vec<String> = self.histogram.iter().map(|(start, count)| format!("{{\"from\":{},\"to\":{},\"count\":{}}}", start, start + self.bucket, count)).collect();
+        let spikes: Vec<String> = self.spikes.iter()
+            .map(|spike| format!("{{\"index\":{},\"depth\":{},\"change\":{},\"z_score\":{:.3}}}", spike.index, spike.depth, spike.change, spike.z_score))
+            .collect();
+        format!(
+            "{{\"readings\":{},\"shallowest\":{},\"deepest\":{},\"increases\":{},\"longest_run\":{{\"start\":{},\"length\":{}}},\"steepest_descent\":{},\"plateaus\":[{}],\"histogram\":[{}],\"z_threshold\":{},\"spikes\":[{}]}}",
+            self.readings, optional(self.shallowest), optional(self.deepest), self.increases, self.longest_run.0, self.longest_run.1,
+            steepest, plateaus.join(","), histogram.join(","), self.z_threshold, spikes.join(","),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(depths: &[i32], z_threshold: f64, bucket: i32) -> Report {
+        let mut report = Report::new(z_threshold, bucket);
+        depths.iter().for_each(|&depth| report.push(depth));
+        report.finish()
+    }
+
+    #[test]
+    fn runs_and_plateaus() {
+        let report = report(&[1, 2, 3, 3, 3, 2, 5, 6, 7, 8, 8], 3.0, 100);
+        assert_eq!(report.readings, 11);
+        assert_eq!((report.shallowest, report.deepest), (Some(1), Some(8)));
+        assert_eq!(report.increases, 6);
+        assert_eq!(report.longest_run, (5, 5));
+        assert_eq!(report.steepest_descent, Some((6, 3)));
+        // the plateau at the end is only counted by finish
+        assert_eq!(report.plateaus, BTreeMap::from([(2, 1), (3, 1)]));
+    }
+
+    #[test]
+    fn spikes_stand_out_from_the_changes_before() {
+        let depths = [0, 1, 3, 4, 6, 7, 9, 109];
+        let report = report(&depths, 3.0, 100);
+        assert_eq!(report.spikes.len(), 1);
+        let spike = &report.spikes[0];
+        assert_eq!((spike.index, spike.depth, spike.change), (7, 109, 100));
+        // changes of 1 and 2 have a mean of 1.5 and a standard deviation of about 0.55
+        assert!((spike.z_score - 98.5 / (0.3f64).sqrt()).abs() < 1e-9, "{}", spike.z_score);
+
+        assert!(super::tests::report(&depths, 200.0, 100).spikes.is_empty());
+        // steady changes have no deviation to measure against
+        assert!(super::tests::report(&[0, 1, 2, 3, 4, 100], 3.0, 100).spikes.is_empty());
+    }
+
+    #[test]
+    fn histogram_bands() {
+        let report = report(&[-1, 0, 99, 100, 250], 3.0, 100);
+        assert_eq!(report.histogram, BTreeMap::from([(-100, 1), (0, 2), (100, 1), (200, 1)]));
+        assert!(report.table().contains("-100..0             1      ####################\n"));
+    }
+
+    #[test]
+    fn json_shape() {
+        assert_eq!(
+            report(&[5, 5], 2.5, 10).json(),
+            "{\"readings\":2,\"shallowest\":5,\"deepest\":5,\"increases\":0,\"longest_run\":{\"start\":0,\"length\":1},\"steepest_descent\":null,\
+             \"plateaus\":[{\"length\":2,\"count\":1}],\"histogram\":[{\"from\":0,\"to\":10,\"count\":2}],\"z_threshold\":2.5,\"spikes\":[]}"
+        );
+        assert!(report(&[], 3.0, 10).json().starts_with("{\"readings\":0,\"shallowest\":null,\"deepest\":null,"));
+    }
+
+    #[test]
+    fn extreme_depths() {
+        let report = report(&[i32::MIN, i32::MAX], 3.0, 100);
+        assert_eq!(report.steepest_descent, Some((1, 4294967295)));
+        assert_eq!(report.histogram, BTreeMap::from([(-2147483700, 1), (2147483600, 1)]));
+        assert!(report.json().contains("{\"from\":2147483600,\"to\":2147483700,\"count\":1}"));
+        assert!(report.table().contains("-2147483700..-2147483600  1 "));
+
+        let report = super::tests::report(&[2147483000, 2147483600, i32::MIN], 3.0, 1000);
+        assert_eq!(report.steepest_descent, Some((1, 600)));
+        assert!(report.json().contains("{\"from\":2147483000,\"to\":2147484000,\"count\":2}"));
+        assert!(report.table().contains("2147483000..2147484000"));
+    }
+}
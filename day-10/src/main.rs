@@ -1,8 +1,8 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
 use std::collections::VecDeque;
+use aoc::input::Input;
 
-fn parse_line(line: &String) -> (bool,i32,i64) {
+fn parse_line(line: &str) -> (bool,i32,i64) {
     let mut depth: VecDeque<char> = VecDeque::new();
     let illegal_to_points = |close: char| -> i32 { 
         match close {
@@ -49,8 +49,8 @@ fn parse_line(line: &String) -> (bool,i32,i64) {
 
 fn main() {
     let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
+    let input = Input::from_stdin();
+    let lines: Vec<&str> = input.lines().collect();
 
     let total_points = lines.iter().map(|line| {
         let (_complete, points, _incomplete_points) =  parse_line(line);
@@ -1,18 +1,55 @@
-use std::io::{self, BufRead, Write, stdout};
+use std::io::{Write, stdout};
 use std::time::{Instant};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::thread::{sleep_ms};
 use crossterm::{
     ExecutableCommand, QueueableCommand,
     terminal, cursor, style::{self, Stylize, Color}, Result
 };
 use structopt::StructOpt;
+use aoc::checkpoint::{Checkpoints, State};
+use aoc::input::Input;
 
 // Commandline arguments
 #[derive(StructOpt)]
 struct Cli {
     visualize: Option<u32>,
-    delay: Option<u32>
+    delay: Option<u32>,
+    /// Save the grid every this many steps, 0 for never
+    #[structopt(long, default_value = "0")]
+    checkpoint_every: usize,
+    /// Checkpoint file, day-11.checkpoint by default
+    #[structopt(long, parse(from_os_str))]
+    checkpoint: Option<PathBuf>,
+    /// Carry on from the saved checkpoint
+    #[structopt(long)]
+    resume: bool
+}
+
+/// The grid between steps, with the flashes counted over the first 100
+struct Cavern {
+    octopuses: [u8; 100],
+    total_flashes_100: i32
+}
+
+impl State for Cavern {
+    fn save(&self) -> String {
+        let mut text = format!("{}\n", self.total_flashes_100);
+        for row in self.octopuses.chunks(10) {
+            text.extend(row.iter().map(|&energy| char::from(b'0' + energy)));
+            text.push('\n');
+        }
+        return text;
+    }
+
+    fn restore(text: &str) -> std::result::Result<Self, String> {
+        let (flashes, grid) = text.split_once('\n').ok_or("missing the grid")?;
+        let total_flashes_100 = flashes.trim().parse::<i32>().map_err(|e| e.to_string())?;
+        let energies: Vec<u8> = grid.lines().flat_map(|line| line.chars()).map(|c| c.to_digit(10).map(|energy| energy as u8)).collect::<Option<_>>().ok_or("expected digits")?;
+        let octopuses: [u8; 100] = energies.try_into().map_err(|_| "expected a 10x10 grid")?;
+        return Ok(Cavern{octopuses, total_flashes_100});
+    }
 }
 
 fn visualize_grid(octopuses: [u8; 100], delay: u32) {
@@ -111,8 +148,8 @@ fn main() {
     let delay: u32 = if args.delay.is_some() { args.delay.unwrap() } else { 0 };
 
     let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
+    let input = Input::from_stdin();
+    let lines: Vec<&str> = input.lines().collect();
 
     let mut octopuses: [u8; 100] = [0; 100];
     lines.iter().enumerate().for_each(|(row,line)| {
@@ -121,19 +158,20 @@ fn main() {
         });
     });
 
-    let mut total_flashes_100 = 0;
-    let mut cur_step = 0;
+    let checkpoints = Checkpoints::new("day-11", args.checkpoint, args.checkpoint_every, args.resume, input.text());
+    let (mut cur_step, mut cavern) = checkpoints.resume().unwrap_or((0, Cavern{octopuses, total_flashes_100: 0}));
     loop {
-        let flashes = step(&mut octopuses, visualize, delay);
-        if cur_step < 100 { total_flashes_100 += flashes; }
-        if octopuses.into_iter().map(|val| val as i32).sum::<i32>() == 0 {
+        let flashes = step(&mut cavern.octopuses, visualize, delay);
+        if cur_step < 100 { cavern.total_flashes_100 += flashes; }
+        if cavern.octopuses.into_iter().map(|val| val as i32).sum::<i32>() == 0 {
             break;
         }
         cur_step += 1;
+        checkpoints.reached(cur_step, &cavern);
     }
     if !visualize {
         println!("\r\nPart 1\r\n{}", "-".repeat(10));
-        println!("Total flashes: {}\r\n", total_flashes_100);
+        println!("Total flashes: {}\r\n", cavern.total_flashes_100);
 
 
         println!("Part 2\r\n{}", "-".repeat(10));
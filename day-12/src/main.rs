@@ -1,125 +1,60 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
-use std::collections::HashMap;
+use aoc::graph::{self, Adjacency};
+use aoc::input::Input;
+use aoc::progress::Progress;
 
-struct Vertice {
-    name: String,
-    is_big: bool,
-    is_start: bool,
-    is_end: bool,
+fn is_big(name: &str) -> bool {
+    return name == name.to_uppercase();
 }
 
-fn vertice_from_name(name: &String) -> Vertice {
-    return Vertice {
-        name: String::from(name),
-        is_big: *name == name.to_uppercase(),
-        is_start: name.to_lowercase() == "start",
-        is_end: name.to_lowercase() == "end",
-    };
+fn parse_line<'a>(line: &'a str, caves: &mut Adjacency<&'a str>) {
+    let (name1, name2) = line.split_once("-").unwrap();
+    let idx1 = caves.node(name1);
+    let idx2 = caves.node(name2);
+    caves.add_undirected(idx1, idx2);
 }
 
-fn parse_line(line: String,v: &mut Vec<Vertice>,adj: &mut Vec<Vec<usize>>) {
-    let names: Vec<&str> = line.split("-").collect();
+fn count_all_walks(caves: &Adjacency<&str>, double_small_visit: bool) -> usize {
+    let start = caves.find(&"start").unwrap();
+    let end = caves.find(&"end").unwrap();
+    let big: Vec<bool> = caves.labels().iter().map(|name| is_big(name)).collect();
 
-    // add vertice or edge
-    let indexes: Vec<usize> = names.into_iter().map(|name| {
-         match v.iter().position(|node| node.name == name) {
-            Some(idx) => return idx,
-            None => {
-                // New vertice
-                v.push(vertice_from_name(&String::from(name)));
-                adj.push(vec!());
-                return v.len() - 1;
-            }
-        }
-    }).collect();
-
-    let idx1 = indexes.iter().nth(0).unwrap();
-    let idx2 = indexes.iter().nth(1).unwrap();
-
-    adj[*idx1].push(*idx2);
-    adj[*idx2].push(*idx1);
-
-}
-
-fn get_all_walks(v: Vec<Vertice>,adj: Vec<Vec<usize>>, double_small_visit: bool) -> Vec<String> {
-    let mut path_list: Vec<String> = vec!();
-    let mut walks: HashMap<String,bool> = HashMap::new();
-    let mut visits_left: Vec<u8> = vec![1; v.len()];
-    let start = v.iter().position(|node| node.name == "start").unwrap();
-    let end = v.iter().position(|node| node.name == "end").unwrap();
-    path_list.push("start".to_string());
+    // progress goes by the choices of a third cave, one per edge out of the start's neighbours
+    let branches = caves.neighbors(start).map(|u| caves.neighbors(u).count()).sum();
+    let mut progress = Progress::new("Counting walks", branches);
 
-    if double_small_visit {
-        let smalls: Vec<usize> = v.iter().enumerate().filter(|(_i,node)| !node.is_big && !node.is_start && !node.is_end).map(|(i,node)| i).collect();
-
-        for small_idx in smalls {
-            visits_left = vec![1; v.len()];
-            visits_left[small_idx] += 1;
-            recurse_walks(start, end, &v, &adj, &mut visits_left, &mut path_list, &mut walks);
+    // big caves can backtrack as many times as they want, small ones once,
+    // or for a single small cave per walk twice (never the start)
+    return graph::count_walks(start, end, |u| caves.neighbors(u), |walk, next| {
+        if walk.len() == 2 {
+            progress.inc(1);
         }
-    }
-    else {
-        recurse_walks(start, end, &v, &adj, &mut visits_left, &mut path_list, &mut walks);
-    }
-
-    return walks.keys().cloned().collect();
-}
-
-fn recurse_walks(u: usize, d: usize,v: &Vec<Vertice>,adj: &Vec<Vec<usize>>,visits_left: &mut Vec<u8>, path_list: &mut Vec<String>,  walks: &mut HashMap<String,bool>) {
-    if u == d {
-        let this_walk = path_list.join(",");
-        if !walks.contains_key(&this_walk) {
-            walks.insert(path_list.join(","), true);
-        }
-        // we found the end, back up
-        return;
-    }
-
-    // mark small nodes as visited, big can backtrack as many times as they want
-    if !v[u].is_big {
-        if visits_left[u] == 0 {
-            return;
+        if big[next] || !walk.contains(&next) {
+            return true;
         }
-        visits_left[u] -=  1;
-    }
-    
-    let c: Vec<usize> = (adj[u]).iter().filter(|x| visits_left[**x] > 0).map(|&x| x).collect();
-    c.iter().for_each(|x| {
-        path_list.push(v[*x].name.to_string());
-        recurse_walks(*x, d, &v, &adj, visits_left,path_list, walks);
-        path_list.remove(path_list.len() - 1);
+        return double_small_visit && next != start && !visits_small_twice(walk, &big);
     });
+}
 
-    if !v[u].is_big {
-        visits_left[u] += 1;
-    }
+fn visits_small_twice(walk: &[usize], big: &[bool]) -> bool {
+    return walk.iter().enumerate().any(|(i, u)| !big[*u] && walk[i + 1..].contains(u));
 }
 
 fn main() {
+    aoc::log::init();
     let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
+    let input = Input::from_stdin();
 
-    let mut v: Vec<Vertice> = vec!();
-    let mut adj: Vec<Vec<usize>> = vec!();
-    lines.iter().for_each(|line| {
-        parse_line(line.to_string(), &mut v, &mut adj);
+    let mut caves: Adjacency<&str> = Adjacency::new();
+    input.lines().for_each(|line| {
+        parse_line(line, &mut caves);
     });
 
-    let walks = get_all_walks(v,adj, false);
-
     println!("Part 1\r\n{}", "-".repeat(10));
-    println!("Number of walks: {}", walks.len());
+    println!("Number of walks: {}", count_all_walks(&caves, false));
 
-    let mut v: Vec<Vertice> = vec!();
-    let mut adj: Vec<Vec<usize>> = vec!();
-    lines.iter().for_each(|line| {
-        parse_line(line.to_string(), &mut v, &mut adj);
-    });
-    let walks = get_all_walks(v,adj, true);
     println!("Part 2\r\n{}", "-".repeat(10));
-    println!("Number of walks with two smalls: {}", walks.len());
+    println!("Number of walks with two smalls: {}", count_all_walks(&caves, true));
 
     let duration = start.elapsed();
     println!("Total execution time: {:?}", duration);
@@ -1,12 +1,11 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
 use std::cmp::Ordering;
+use aoc::geometry::{Bounds, Vec2};
+use aoc::input::Input;
+use aoc::parse::{self, integer, literal, map, one_of, preceded, separated_pair};
+use aoc::log::{self, Level};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-struct Point {
-    x: u16,
-    y: u16
-}
+type Point = Vec2<u16>;
 
 
 #[derive(Debug)]
@@ -20,11 +19,11 @@ struct Fold {
 
 fn display_points(points: &Vec<Point>, rows: u16, cols: u16) {
     for col in 0..cols {
-        for row in 0..rows {
+        let line: String = (0..rows).map(|row| {
             let idx = points.iter().position(|p| p.x == row && p.y == col);
-            print!("{}", if idx.is_some() {"#"} else { "."});
-        }
-        println!();
+            if idx.is_some() {'#'} else {'.'}
+        }).collect();
+        aoc::info!("{}", line);
     }
 
 }
@@ -54,36 +53,21 @@ fn fold_along(fold: &Fold, points: &mut Vec<Point>, rows: &mut u16, cols: &mut u
 }
 
 fn main() {
+    log::init();
     let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
-    let mut points: Vec<Point> = lines.iter().filter_map(|line| {
-                               match line.contains(",") {
-                                   false => None,
-                                   true => {
-                                        let mut cord = line.split(",");
-                                        let x = cord.next().unwrap().parse::<u16>().unwrap();
-                                        let y = cord.next().unwrap().parse::<u16>().unwrap();
-                                        return Some(Point { x: x, y: y});
-                                   }
-                               }
-                            }).collect();
+    let input = Input::from_stdin();
+    let mut sections = input.sections();
+    // 6,10
+    let point = separated_pair(integer::<u16>(), literal(","), integer::<u16>());
+    let mut points: Vec<Point> = parse::parse(parse::lines(map(point, |(x, y)| Point::new(x, y))), sections.next().unwrap()).unwrap();
                             
-    let mut rows = points.iter().map(|point| point.x).max().unwrap() + 1;
-    let mut cols = points.iter().map(|point| point.y).max().unwrap() + 1;
-
-    let mut folds: Vec<Fold> = lines.iter().filter_map(|line| {
-            match line.contains("fold along ") {
-                false => None,
-                true => {
-                        let trimmed = line.replacen("fold along ","",1);
-                        let mut cord = trimmed.split("=");
-                        let direction = cord.next().unwrap().chars().nth(0).unwrap();
-                        let value = cord.next().unwrap().parse::<u16>().unwrap();
-                        return Some(Fold { direction: direction, value: value});
-                }
-            }
-            }).collect();
+    let paper = Bounds::from_points(points.iter().copied()).unwrap();
+    let mut rows = paper.max.x + 1;
+    let mut cols = paper.max.y + 1;
+
+    // fold along y=7
+    let fold = preceded(literal("fold along "), separated_pair(one_of("xy"), literal("="), integer::<u16>()));
+    let folds: Vec<Fold> = parse::parse(parse::lines(map(fold, |(direction, value)| Fold { direction: direction, value: value })), sections.next().unwrap()).unwrap();
 
 
     fold_along(&folds[0], &mut points, &mut rows, &mut cols);
@@ -94,10 +78,12 @@ fn main() {
     for i in 1..folds.len() {
         fold_along(&folds[i], &mut points, &mut rows, &mut cols);
     }
-    display_points(&points, rows, cols);
 
     println!("Part 2\r\n{}", "-".repeat(10));
-    // todo
+    // todo, read the code off the paper with -v
+    if log::enabled(Level::Info) {
+        display_points(&points, rows, cols);
+    }
 
     let duration = start.elapsed();
     println!("Total execution time: {:?}", duration);
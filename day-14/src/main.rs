@@ -1,31 +1,33 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
 use std::collections::HashMap;
+use aoc::checkpoint::{Checkpoints, State};
+use aoc::input::Input;
+use aoc::parse::{self, alpha, integer, literal, separated_pair};
 
-fn rule_to_pairs(base_pair: &String, insert: String) -> Vec<String> {
+fn rule_to_pairs(base_pair: &str, insert: &str) -> Vec<String> {
     let mut pairs: Vec<String> = vec!();
     pairs.push(base_pair.to_string());
 
     for (i, letter) in base_pair.chars().enumerate().collect::<Vec<(_,_)>>() {
         let mut split: String = String::new();
         if i == 0 {
-             split.push_str(letter.to_string().as_str());
-             split.push_str(insert.as_str());
+             split.push(letter);
+             split.push_str(insert);
         }
         else {
-            split.push_str(insert.as_str());
-            split.push_str(letter.to_string().as_str());
+            split.push_str(insert);
+            split.push(letter);
         }
         pairs.push(split);
     }
     return pairs;
 }
 
-fn create_map_from_rules(lines: &Vec<String>) -> (HashMap<String, u64>, HashMap<String,(String,String)>) {
+fn create_map_from_rules<'a>(rule_list: &[(&'a str, &str)]) -> (HashMap<String, u64>, HashMap<&'a str,(String,String)>) {
 
     let mut polymers: HashMap<String, u64> = HashMap::new();
-    let mut rules: HashMap<String,(String,String)> = HashMap::new();
-    lines.iter().enumerate().filter(|&(i,_)| i > 1).for_each(|(_,line)| {
+    let mut rules: HashMap<&'a str,(String,String)> = HashMap::new();
+    rule_list.iter().for_each(|&(base_pair, insert)| {
         // for slice in line.chars().collect::<Vec<_>>().windows(2) {
         //     println!("{:?}", slice);
         // }
@@ -34,10 +36,7 @@ fn create_map_from_rules(lines: &Vec<String>) -> (HashMap<String, u64>, HashMap<
         // for letter in base_pair.chars().collect::<Vec<_>>() {
 
         // }
-        let mut rule = line.split(" -> ");
-        let base_pair = rule.next().unwrap().to_string();
-        let insert = rule.next().unwrap().to_string();
-        let pairs = rule_to_pairs(&base_pair,insert);
+        let pairs = rule_to_pairs(base_pair,insert);
 
         // add to our polymer map counts
         for pair in pairs.iter() {
@@ -53,14 +52,14 @@ fn create_map_from_rules(lines: &Vec<String>) -> (HashMap<String, u64>, HashMap<
     return (polymers, rules);
 }
 
-fn populate_from_template(template: String, polymers: &mut HashMap<String, u64>) {
+fn populate_from_template(template: &str, polymers: &mut HashMap<String, u64>) {
     for slice in template.chars().collect::<Vec<_>>().windows(2) {
         let pair: String = slice.iter().collect();
         *polymers.get_mut(&pair).unwrap() += 1;
     }
 }
 
-fn score_polymers(polymers: HashMap<String, u64>, last_letter: String) -> u64 {
+fn score_polymers(polymers: &HashMap<String, u64>, last_letter: String) -> u64 {
     let mut occurrances: HashMap<String,u64> = HashMap::new();
     polymers.keys().for_each(|base_pair| {
         let letter = base_pair.chars().nth(0).unwrap();
@@ -77,45 +76,87 @@ fn score_polymers(polymers: HashMap<String, u64>, last_letter: String) -> u64 {
     return max_letter_count - min_letter_count;
 }
 
-fn polymer_iterations(template: &String, lines: &Vec<String>, iterations: usize) -> u64 {
-    let (mut polymers, rules) = create_map_from_rules(&lines);
-    populate_from_template(template.to_string(), &mut polymers);
+/// Pair counts between steps, and the part 1 score once reached
+struct Polymer {
+    polymers: HashMap<String, u64>,
+    score_10: Option<u64>
+}
+
+impl State for Polymer {
+    fn save(&self) -> String {
+        let mut pairs: Vec<(&String, &u64)> = self.polymers.iter().collect();
+        pairs.sort();
+        let mut text = format!("{}\n", self.score_10.map_or("-".to_string(), |score| score.to_string()));
+        pairs.iter().for_each(|(pair, count)| text.push_str(&format!("{} {}\n", pair, count)));
+        return text;
+    }
 
-    
-    for _n in 0..iterations {
+    fn restore(text: &str) -> Result<Self, String> {
+        let (score_10, pairs) = text.split_once('\n').ok_or("missing the pair counts")?;
+        let score_10 = match score_10.trim() {
+            "-" => None,
+            score => Some(score.parse::<u64>().map_err(|e| e.to_string())?),
+        };
+        let pair = separated_pair(alpha(), literal(" "), integer::<u64>());
+        let polymers = parse::parse(parse::lines(pair), pairs).map_err(|e| e.to_string())?;
+        return Ok(Polymer{polymers: polymers.into_iter().map(|(pair, count)| (pair.to_string(), count)).collect(), score_10});
+    }
+}
+
+fn polymer_iterations(template: &str, rule_list: &[(&str, &str)], iterations: usize, checkpoints: &Checkpoints) -> Polymer {
+    let _span = aoc::span!("polymer_iterations {}", iterations);
+    let last_letter = template.chars().last().unwrap().to_string();
+    let (mut polymers, rules) = create_map_from_rules(rule_list);
+    populate_from_template(template, &mut polymers);
+
+    let (mut n, mut polymer) = checkpoints.resume().unwrap_or((0, Polymer{polymers, score_10: None}));
+    while n < iterations {
+        let _span = aoc::span!("iteration {}", n);
         let mut tally: HashMap<String,u64> = HashMap::new();
-        polymers.keys().for_each(|base_pair| {
-            let (split_1,split_2) = rules.get(base_pair).unwrap();
-            let num_pairs = polymers.get(base_pair).unwrap();
+        polymer.polymers.keys().for_each(|base_pair| {
+            let (split_1,split_2) = rules.get(base_pair.as_str()).unwrap();
+            let num_pairs = polymer.polymers.get(base_pair).unwrap();
 
             *tally.entry(split_1.to_string()).or_insert(0) += num_pairs;
             *tally.entry(split_2.to_string()).or_insert(0) += num_pairs;
         });
         
-        polymers.clear();
+        polymer.polymers.clear();
         tally.keys().for_each(|base_pair| {
-            polymers.insert(base_pair.to_string(), *tally.get(base_pair).unwrap());
+            polymer.polymers.insert(base_pair.to_string(), *tally.get(base_pair).unwrap());
         });
+        n += 1;
+        if n == 10 {
+            polymer.score_10 = Some(score_polymers(&polymer.polymers, last_letter.clone()));
+        }
+        checkpoints.reached(n, &polymer);
     }
 
-    return score_polymers(polymers, template.chars().last().unwrap().to_string());
+    return polymer;
 }
 
 
 fn main() {
+    aoc::log::init();
+    let _trace = aoc::trace::init();
     let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
+    let input = Input::from_stdin();
+    let checkpoints = Checkpoints::from_args("day-14", input.text());
+    let mut sections = input.sections();
+
+    let template: &str = sections.next().unwrap();
+    // CH -> B
+    let rule = separated_pair(alpha(), literal(" -> "), alpha());
+    let rule_list: Vec<(&str, &str)> = parse::parse(parse::lines(rule), sections.next().unwrap()).unwrap();
 
-    let template: &String = lines.iter().nth(0).unwrap();
-    let score = polymer_iterations(template, &lines, 10);
+    let polymer = polymer_iterations(template, &rule_list, 40, &checkpoints);
 
     println!("Part 1\r\n{}", "-".repeat(10));
-    println!("Score: {}\r\n", polymer_iterations(template, &lines, 10));
+    println!("Score: {}\r\n", polymer.score_10.unwrap());
 
   
     println!("Part 2\r\n{}", "-".repeat(10));
-    println!("Score: {}", polymer_iterations(template, &lines, 40));
+    println!("Score: {}", score_polymers(&polymer.polymers, template.chars().last().unwrap().to_string()));
 
     let duration = start.elapsed();
     println!("Total execution time: {:?}", duration);
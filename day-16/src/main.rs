@@ -1,7 +1,7 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
+use aoc::input::Input;
 
-fn hexstring_to_bytes(hex: &String) -> Vec<u8> {
+fn hexstring_to_bytes(hex: &str) -> Vec<u8> {
     return (0..hex.len()).step_by(2).map(|i| {
         u8::from_str_radix(&hex[i..i + 2], 16).unwrap()
     }).collect();
@@ -190,9 +190,8 @@ impl BITSParser {
 
 fn main() {
     let start = Instant::now();
-    let stdin = io::stdin();
-    let line: String = stdin.lock().lines().nth(0).unwrap().unwrap();
-    let bytes = hexstring_to_bytes(&line);
+    let input = Input::from_stdin();
+    let bytes = hexstring_to_bytes(input.first_line());
     let parser = BITSParser::new(bytes).parse();
         
     println!("Part 1\r\n{}", "-".repeat(10));
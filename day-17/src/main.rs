@@ -1,32 +1,28 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
 use std::collections::BinaryHeap;
 use std::cmp::Ordering;
+use aoc::geometry::{Bounds, Vec2};
+use aoc::input::Input;
+use aoc::parse::{self, integer, literal, preceded, separated_pair};
 
 #[derive(Debug)]
 struct Target {
-    x1: i32,
-    x2: i32,
-    y1: i32,
-    y2: i32
+    area: Bounds<Vec2<i32>>
 }
 
 #[derive(Debug,Eq, PartialEq, Copy, Clone)]
 struct Probe {
-    x: i32,
-    y: i32,
-    dx: i32,
-    dy: i32,
-    cur_dx: i32,
-    cur_dy: i32,
+    position: Vec2<i32>,
+    launch: Vec2<i32>,
+    velocity: Vec2<i32>,
     max_y: i32
 }
 
 impl Ord for Probe {
     fn cmp(&self, other: &Probe) -> Ordering {
         self.max_y.cmp(&other.max_y)
-            .then_with(|| other.x.cmp(&self.x))
-            .then_with(|| other.y.cmp(&self.y))
+            .then_with(|| other.position.x.cmp(&self.position.x))
+            .then_with(|| other.position.y.cmp(&self.position.y))
     }
 }
 
@@ -38,43 +34,39 @@ impl PartialOrd for Probe {
 
 impl Probe {
     fn new(dx: i32, dy: i32) -> Self {
+        let launch = Vec2::new(dx, dy);
         Probe {
-            x: 0,
-            y: 0,
-            dx: dx,
-            dy: dy,
-            cur_dx: dx,
-            cur_dy: dy,
+            position: Vec2::default(),
+            launch: launch,
+            velocity: launch,
             max_y: 0
         }
     }
 
     fn step(&mut self) {
-        self.x += self.cur_dx;
-        self.y += self.cur_dy;
-        self.max_y = i32::max(self.y, self.max_y);
+        self.position += self.velocity;
+        self.max_y = i32::max(self.position.y, self.max_y);
 
-        if self.cur_dx > 0 { self.cur_dx -= 1} else if self.cur_dx < 0 { self.cur_dx += 1}
-        self.cur_dy -= 1;
+        // drag towards zero horizontally, gravity vertically
+        self.velocity -= Vec2::new(self.velocity.x.signum(), 1);
     }
 }
 
 impl std::fmt::Display for Probe {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "dx {}, dy: {}", self.dx, self.dy)
+        write!(f, "dx {}, dy: {}", self.launch.x, self.launch.y)
     }
 }
 
 impl Target {
-    fn new(line: String) -> Self {
-        let clean = line.replacen("target area: ", "", 1).replacen("x=","",1).replacen(" y=","",1).replacen("..",",",2);
-        let cordinates: Vec<i32> = clean.split(",").map(|c| c.parse::<i32>().unwrap()).collect();
+    fn new(line: &str) -> Self {
+        // target area: x=20..30, y=-10..-5
+        let range = |axis| preceded(literal(axis), separated_pair(integer::<i32>(), literal(".."), integer::<i32>()));
+        let target = preceded(literal("target area: "), separated_pair(range("x="), literal(", "), range("y=")));
+        let ((x1, x2), (y1, y2)) = parse::parse(target, line).unwrap();
 
         Target {
-            x1: *cordinates[0..=1].iter().min().unwrap(),
-            x2: *cordinates[0..=1].iter().max().unwrap(),
-            y1: *cordinates[2..=3].iter().min().unwrap(),
-            y2: *cordinates[2..=3].iter().max().unwrap()
+            area: Bounds::new(Vec2::new(x1, y1), Vec2::new(x2, y2))
         }
     }
     /// returns true if a given 
@@ -82,13 +74,12 @@ impl Target {
         loop {
             probe.step();
             // too far to the right or below target
-            if probe.x > self.x2 || probe.y < self.y1 {
+            if probe.position.x > self.area.max.x || probe.position.y < self.area.min.y {
                 return false;            
             }
 
             // hit
-            if probe.x >= self.x1 && probe.x <= self.x2 &&
-               probe.y >= self.y1 && probe.y <= self.y2 {
+            if self.area.contains(probe.position) {
                 return true;
             }
         }
@@ -98,14 +89,13 @@ impl Target {
 
 fn main() {
     let start = Instant::now();
-    let stdin = io::stdin();
-    let line: String = stdin.lock().lines().nth(0).unwrap().unwrap();
+    let input = Input::from_stdin();
 
     let mut max_height: BinaryHeap<Probe> = BinaryHeap::new();
-    let mut target = Target::new(line);
+    let mut target = Target::new(input.first_line());
 
-    for x in 0..=target.x2 {
-        for y in target.y1..=i32::abs(target.y1) {
+    for x in 0..=target.area.max.x {
+        for y in target.area.min.y..=i32::abs(target.area.min.y) {
             let mut probe = Probe::new(x,y);
             if target.does_it_hit(&mut probe) {
                 max_height.push(probe);
@@ -113,14 +103,14 @@ fn main() {
         }
     }
 
-    println!("Part 1\r\n{}", "-".repeat(10));
-    println!("Number of hits: {}", max_height.len());
-    
+    let num_hits = max_height.len();
     let probe = max_height.pop().unwrap();
-    println!("Max height: {} - {}", probe.max_y, probe);
+    println!("Part 1\r\n{}", "-".repeat(10));
+    println!("Highest probe: {}", probe);
+    println!("Max height: {}\r\n", probe.max_y);
 
     println!("Part 2\r\n{}", "-".repeat(10));
-    // todo
+    println!("Number of hits: {}\r\n", num_hits);
 
     let duration = start.elapsed();
     println!("Total execution time: {:?}", duration);
@@ -1,18 +1,18 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
 use std::ops;
 use std::collections::BinaryHeap;
+use aoc::input::Input;
 
-fn peek_char(s: &mut String) -> char {
+fn peek_char(s: &mut &str) -> char {
     return s.chars().nth(0).unwrap();
 }
 
-fn pop_char(s: &mut String) -> Option<char> {
+fn pop_char(s: &mut &str) -> Option<char> {
     if s.len() == 0 {
         return None;
     }
     let c = peek_char(s);
-    s.drain(..=0);
+    *s = &s[c.len_utf8()..];
     return Some(c);
 }
 
@@ -38,12 +38,12 @@ impl SnailNumber {
         SnailNumber { numbers: vec!()}
     }
 
-    fn parse(&mut self, line: &mut String) {
+    fn parse(&mut self, mut line: &str) {
         self.numbers.clear();
-        self.parse_recur(line, 0);
+        self.parse_recur(&mut line, 0);
     }
 
-    fn parse_recur(&mut self, line: &mut String, depth: usize) {
+    fn parse_recur(&mut self, line: &mut &str, depth: usize) {
         if peek_char(line) == '[' {
             pop_char(line); // [
             self.parse_recur(line, depth + 1);
@@ -56,16 +56,9 @@ impl SnailNumber {
         }
         else {
             // Must be number
-            let mut num_str: String = "".to_string();
-            loop {
-                match peek_char(line) {
-                    ']' | ',' => break,
-                    c => {
-                        pop_char(line);
-                        num_str.push_str(&c.to_string());
-                    }
-                };
-            }
+            let num_len = line.find(|c| c == ']' || c == ',').unwrap();
+            let num_str = &line[..num_len];
+            *line = &line[num_len..];
             let number = match num_str.parse::<u32>() {
                 Ok(number) => number,
                 Err(_e) => unreachable!(),
@@ -173,10 +166,9 @@ fn largest_pair_magnitude(numbers: Vec<SnailNumber>) -> u32 {
 
 fn main() {
     let start = Instant::now();
-    let stdin = io::stdin();
-    let mut lines: Vec<String> = stdin.lock().lines().flatten().collect();
+    let input = Input::from_stdin();
 
-    let numbers: Vec<SnailNumber> = lines.iter_mut().map(|line| {
+    let numbers: Vec<SnailNumber> = input.lines().map(|line| {
             let mut num = SnailNumber::new();
             num.parse(line);
             num.reduce();
@@ -1,8 +1,9 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::fmt;
+use aoc::geometry::{Rotation, Vec3};
+use aoc::input::Input;
+use aoc::progress::Progress;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct DistanceMatch {
@@ -29,100 +30,27 @@ impl PartialOrd for DistanceMatch {
 
 
 #[derive(Debug)]
-struct Scanner {
-    name: String,
+struct Scanner<'a> {
+    name: &'a str,
     becons: Vec<Point>,
     location: Point
 }
 
-#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
-struct Point {
-    x: i32,
-    y: i32,
-    z: i32
-}
-
-impl Point {
-    fn new(line: &String) -> Self {
-        let s = line.split(",").collect::<Vec<_>>();
-        let mut iter = s.iter();
-        let x = iter.next().unwrap().parse::<i32>().unwrap();
-        let y = iter.next().unwrap().parse::<i32>().unwrap();
-        let z = iter.next().unwrap().parse::<i32>().unwrap();
-        return Point {x: x, y: y, z: z};
-    }
-
-    fn add(self, v: &Point) -> Point {
-        return Point{ x: self.x + v.x, y: self.y + v.y, z: self.z + v.z };
-    }
-
-    fn subtract(self, v: &Point) -> Point {
-        return Point{ x: self.x - v.x, y: self.y - v.y, z: self.z - v.z };
-    }
-
-    fn rotate(&self, rotation: usize) -> Point {
-        let rotated = match rotation {
-            0  => ( self.x,  self.y,  self.z),
-            1  => ( self.x,  self.z, -self.y),
-            2  => ( self.x, -self.y, -self.z),
-            3  => ( self.x, -self.z,  self.y),
-            4  => ( self.y,  self.x, -self.z),
-            5  => ( self.y,  self.z,  self.x),
-            6  => ( self.y, -self.x,  self.z),
-            7  => ( self.y, -self.z, -self.x),
-            8  => ( self.z,  self.x,  self.y),
-            9  => ( self.z,  self.y, -self.x),
-            10 => ( self.z, -self.x, -self.y),
-            11 => ( self.z, -self.y,  self.x),
-            12 => (-self.x,  self.y, -self.z),
-            13 => (-self.x,  self.z,  self.y),
-            14 => (-self.x, -self.y,  self.z),
-            15 => (-self.x, -self.z, -self.y),
-            16 => (-self.y,  self.x,  self.z),
-            17 => (-self.y,  self.z, -self.x),
-            18 => (-self.y, -self.x, -self.z),
-            19 => (-self.y, -self.z,  self.x),
-            20 => (-self.z,  self.x, -self.y),
-            21 => (-self.z,  self.y,  self.x),
-            22 => (-self.z, -self.x,  self.y),
-            23 => (-self.z, -self.y, -self.x),
-            _ => unreachable!()
-        };
-        return Point{ x: rotated.0, y: rotated.1, z: rotated.2 }
-    }
-
-    fn manhattan_distance(&self, other: &Point) -> i32 {
-        return i32::abs(self.x - other.x) + i32::abs(self.y - other.y) + i32::abs(self.z - other.z);
-    }
-}
-
-impl fmt::Display for Point {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({}, {}, {})", self.x, self.y, self.z)
-    }
-}
+type Point = Vec3<i32>;
 
-impl Ord for Point {
-    fn cmp(&self, other: &Point) -> Ordering {
-        self.x.cmp(&other.x)
-            .then_with(|| self.y.cmp(&other.y))
-            .then_with(|| self.z.cmp(&other.z))
-    }
-}
-
-impl PartialOrd for Point {
-    fn partial_cmp(&self, other: &Point) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+fn parse_point(line: &str) -> Point {
+    let mut iter = line.split(",").map(|c| c.parse::<i32>().unwrap());
+    return Point::new(iter.next().unwrap(), iter.next().unwrap(), iter.next().unwrap());
 }
 
 
-impl Scanner {
-    fn new(lines: Vec<String>) -> Self {
-        let mut scanner = Scanner{name: lines[0].to_string(), becons: vec!(), location: Point{x:0,y:0,z:0}};
+impl<'a> Scanner<'a> {
+    fn new(section: &'a str) -> Self {
+        let mut lines = section.lines();
+        let mut scanner = Scanner{name: lines.next().unwrap(), becons: vec!(), location: Point{x:0,y:0,z:0}};
 
-        for line in &lines[1..] {
-            scanner.becons.push(Point::new(line));
+        for line in lines {
+            scanner.becons.push(parse_point(line));
         }
         return scanner
     }
@@ -130,11 +58,12 @@ impl Scanner {
     fn rotate_and_locate(&mut self, rotation: usize, location: Point) {
         self.location = location;
         for i in 0..self.becons.len() {
-            self.becons[i] = self.becons[i].rotate(rotation).add(&location);
+            self.becons[i] = Rotation::ALL[rotation] * self.becons[i] + location;
         }
     }
 
     fn matching_sets(&self, other: &Scanner, threshold: usize) -> Option<(usize, Point, Vec<DistanceMatch>)> {
+        let _span = aoc::span!("matching_sets");
         let mut matching_becons: Vec<DistanceMatch> = vec!();
         // compare every Point relative to the other scanners Points
         // find the largest matching distance set
@@ -153,9 +82,9 @@ impl Scanner {
         // find correct rotation based upon offset of all matching pairs
         // until all of them are equal
         let mut distance_map: HashMap<Point,u32> = HashMap::new();
-        for rotation in 0..24 {
+        for rotation in 0..Rotation::ALL.len() {
             'pairs: for pair in &matching_becons {
-                distance_map.entry(self.becons[pair.a_Point_idx].subtract(&other.becons[pair.b_Point_idx].rotate(rotation)))
+                distance_map.entry(self.becons[pair.a_Point_idx] - Rotation::ALL[rotation] * other.becons[pair.b_Point_idx])
                             .and_modify(|num_same| *num_same += 1).or_insert(1);
                 if distance_map.len() > 1 {
                     break 'pairs;  // all must be equadistant
@@ -170,7 +99,7 @@ impl Scanner {
                 }
                 // Figure out position of other scanner relative to use
                 let other_location = self.becons[matching_becons[0].a_Point_idx]
-                        .subtract(&other.becons[matching_becons[0].b_Point_idx].rotate(rotation));
+                        - Rotation::ALL[rotation] * other.becons[matching_becons[0].b_Point_idx];
 
                 return Some((rotation, other_location, matching_becons));
             }
@@ -186,7 +115,7 @@ impl Scanner {
     
     fn distances_from(&self, idx: usize) -> Vec<u64> {
         let mut distances: Vec<u64> = self.becons.iter().enumerate().filter(|&(i,_)| i != idx).map(|(_,v)| {
-            euclid_distance(&self.becons[idx], &v)
+            self.becons[idx].distance_squared(*v) as u64
         }).collect::<Vec<u64>>();
         distances.sort();
         return distances;
@@ -202,10 +131,6 @@ impl Scanner {
 }
 
 
-fn euclid_distance(u: &Point, v: &Point) -> u64 {
-    return ((u.x as f64 - v.x as f64).powf(2.0) + (u.y as f64 - v.y as f64).powf(2.0) + (u.z as f64 - v.z as f64).powf(2.0)) as u64;
-}
-
 fn intersection_count_sorted_vec(a: &Vec<u64>, b: &Vec<u64>) -> usize {
     let mut count = 0;
     let mut b_iter = b.iter();
@@ -226,10 +151,13 @@ fn intersection_count_sorted_vec(a: &Vec<u64>, b: &Vec<u64>) -> usize {
 }
 
 fn normalize(scanners: &mut Vec<Scanner>) {
+    let _span = aoc::span!("normalize");
     let mut work_queue: Vec<usize> = vec!();
     let mut finished: HashMap<usize,bool> = HashMap::new();
     finished.insert(0, true);
     work_queue.push(0);
+    let mut progress = Progress::new("Locating scanners", scanners.len());
+    progress.set(1);
     
     while let Some(a) = work_queue.pop() {
         for b in (0..scanners.len()).filter(|&b| b != a && !finished.contains_key(&b)).collect::<Vec<usize>>() {
@@ -240,9 +168,10 @@ fn normalize(scanners: &mut Vec<Scanner>) {
                     // add all of becons to scanner, since we know their relative location
                     let b_becons = scanners[b].becons.clone();
                     &scanners[0].merge(&(b_becons));
-                    println!("Scanner {}, location {}", b, b_location);
+                    aoc::debug!("Scanner {}, location {}", b, b_location);
                     work_queue.push(b);
                     finished.insert(b, true);
+                    progress.set(finished.len());
                 } 
             }
         }
@@ -251,24 +180,12 @@ fn normalize(scanners: &mut Vec<Scanner>) {
 
 
 fn main() {
+    let _trace = aoc::trace::init();
+    aoc::log::init();
     let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
+    let input = Input::from_stdin();
     // split our input a blank line delimiters
-    let scanner_lines = lines.iter().fold(vec!(), |mut acc: Vec<Vec<String>>, line| {
-        let mut skip = false;
-        if line.len() == 0 || acc.is_empty() {
-            skip = !acc.is_empty();
-            acc.push(vec!());
-        }
-        
-        if !skip {
-            acc.last_mut().unwrap().push(line.to_string());
-        }
-        acc
-    });
-
-    let mut scanners: Vec<Scanner> = scanner_lines.iter().map(|lines| Scanner::new(lines.to_vec())).collect();
+    let mut scanners: Vec<Scanner> = input.sections().map(|section| Scanner::new(section)).collect();
 
     normalize(&mut scanners);
 
@@ -278,7 +195,7 @@ fn main() {
     let mut scanner_distances: Vec<i32> = vec!();
     for a in 0..scanners.len() {
         for b in a+1..scanners.len() {
-            scanner_distances.push(scanners[a].location.manhattan_distance(&scanners[b].location));        }
+            scanner_distances.push(scanners[a].location.manhattan(scanners[b].location));        }
     }
 
     println!("Part 2\r\n{}", "-".repeat(10));
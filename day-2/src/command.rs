@@ -0,0 +1,119 @@
+use std::fmt;
+
+use aoc::parse::{self, alpha, integer, newline, preceded, terminated, Failure, PResult, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Forward(units) => write!(f, "forward {}", units),
+            Command::Down(units) => write!(f, "down {}", units),
+            Command::Up(units) => write!(f, "up {}", units),
+        }
+    }
+}
+
+/// `forward 5`, `down 3` or `up 2`, with any spaces or tabs around or
+/// between them
+pub fn command<'a>() -> impl Parser<'a, Command> {
+    let units = preceded(blanks, integer::<i32>());
+    move |input: &'a str| -> PResult<'a, Command> {
+        let input = input.trim_start_matches(BLANKS);
+        let (direction, rest) = alpha()(input)?;
+        let command: fn(i32) -> Command = match direction {
+            "forward" => Command::Forward,
+            "down" => Command::Down,
+            "up" => Command::Up,
+            _ => return Err(Failure { expected: "forward, down or up".to_string(), rest: input }),
+        };
+        let (units, rest) = units(rest)?;
+        Ok((command(units), rest.trim_start_matches(BLANKS)))
+    }
+}
+
+const BLANKS: [char; 2] = [' ', '\t'];
+
+/// At least one space or tab
+fn blanks(input: &str) -> PResult<'_, ()> {
+    match input.trim_start_matches(BLANKS) {
+        rest if rest.len() < input.len() => Ok(((), rest)),
+        _ => Err(Failure { expected: "a space".to_string(), rest: input }),
+    }
+}
+
+/// A line break and any blank lines after it
+fn line_breaks(input: &str) -> PResult<'_, ()> {
+    let (_, rest) = newline()(input)?;
+    blank_lines(rest)
+}
+
+/// Any number of lines holding nothing but spaces or tabs
+fn blank_lines(mut rest: &str) -> PResult<'_, ()> {
+    loop {
+        let trimmed = rest.trim_start_matches(BLANKS);
+        match newline()(trimmed) {
+            Ok((_, after)) => rest = after,
+            Err(_) if trimmed.is_empty() => return Ok(((), trimmed)),
+            Err(_) => return Ok(((), rest)),
+        }
+    }
+}
+
+/// One command per line, skipping blank lines and failing with the line and
+/// column of the first bad one
+pub fn parse_commands(text: &str) -> Result<Vec<Command>, parse::Error> {
+    let commands = parse::separated(command(), line_breaks);
+    parse::parse(preceded(blank_lines, terminated(commands, blank_lines)), text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+
+    fn error(text: &str) -> (usize, usize, String) {
+        let error = parse_commands(text).unwrap_err();
+        (error.line, error.column, error.to_string())
+    }
+
+    #[test]
+    fn example() {
+        let commands = parse_commands(EXAMPLE).unwrap();
+        assert_eq!(commands[..3], [Command::Forward(5), Command::Down(5), Command::Forward(8)]);
+        assert_eq!(commands.len(), 6);
+        assert_eq!(commands.iter().map(|command| format!("{}\n", command)).collect::<String>(), EXAMPLE);
+    }
+
+    #[test]
+    fn tabs_and_spaces() {
+        assert_eq!(parse_commands("  forward\t5 \n\tdown  \t 3\r\nup    2\t"), Ok(vec!(Command::Forward(5), Command::Down(3), Command::Up(2))));
+        assert_eq!(error("forward5\n").2, "line 1, column 8: expected a space, found \"5\"");
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        assert_eq!(parse_commands("forward 5\ndown 5\n\nforward 8\n"), Ok(vec!(Command::Forward(5), Command::Down(5), Command::Forward(8))));
+        assert_eq!(parse_commands("\nup 1\n \t\n\r\nup 2\n\n  "), Ok(vec!(Command::Up(1), Command::Up(2))));
+        // and don't throw off the line numbers after them
+        assert_eq!(error("forward 5\n\n\ndown x\n").2, "line 4, column 6: expected an integer, found \"x\"");
+    }
+
+    #[test]
+    fn unknown_direction() {
+        assert_eq!(error("forward 5\nback 3\n").2, "line 2, column 1: expected forward, down or up, found \"back 3\"");
+        assert_eq!(error("forward 5\n  7\n"), (2, 3, "line 2, column 3: expected letters, found \"7\"".to_string()));
+    }
+
+    #[test]
+    fn integer_out_of_range() {
+        assert_eq!(parse_commands("down 2147483647\nup -2147483648"), Ok(vec!(Command::Down(i32::MAX), Command::Up(i32::MIN))));
+        assert_eq!(error("down 1\nforward 2147483648\n").2, "line 2, column 9: expected an integer in range, found \"2147483648\"");
+    }
+}
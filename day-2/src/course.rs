@@ -0,0 +1,143 @@
+use crate::command::Command;
+use crate::navigation::{NavigationModel, Overflow};
+
+/// Where a model has the submarine after a number of commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub step: usize,
+    pub horizontal: i64,
+    pub depth: i64,
+    pub aim: Option<i64>,
+}
+
+/// Every position along a course under one model, starting from step 0
+/// before any command
+pub struct Course {
+    pub model: &'static str,
+    pub steps: Vec<Step>,
+}
+
+impl Course {
+    pub fn trace<M: NavigationModel>(model: &'static str, mut navigation: M, commands: &[Command]) -> Result<Self, Overflow> {
+        let step = |step: usize, navigation: &M| Step { step, horizontal: navigation.horizontal(), depth: navigation.depth(), aim: navigation.aim() };
+        let mut steps = vec![step(0, &navigation)];
+        for (idx, &command) in commands.iter().enumerate() {
+            navigation.apply(command).ok_or(Overflow { step: idx + 1, command })?;
+            steps.push(step(idx + 1, &navigation));
+        }
+        Ok(Course { model, steps })
+    }
+
+    /// The first step ending deeper than `depth`
+    pub fn first_deeper_than(&self, depth: i64) -> Option<&Step> {
+        self.steps.iter().find(|step| step.depth > depth)
+    }
+
+    /// The first step at the course's greatest depth
+    pub fn deepest(&self) -> &Step {
+        // max_by_key keeps the last of equals, reversing makes that the first
+        self.steps.iter().rev().max_by_key(|step| step.depth).unwrap()
+    }
+}
+
+/// All the courses' steps as CSV, a row per model and step
+pub fn csv(courses: &[Course]) -> String {
+    let mut text = "model,step,horizontal,depth,aim\n".to_string();
+    for course in courses {
+        for step in course.steps.iter() {
+            let aim = step.aim.map_or(String::new(), |aim| aim.to_string());
+            text.push_str(&format!("{},{},{},{},{}\n", course.model, step.step, step.horizontal, step.depth, aim));
+        }
+    }
+    text
+}
+
+const WIDTH: f64 = 800.0;
+const PANEL_HEIGHT: f64 = 300.0;
+const MARGIN: f64 = 50.0;
+
+/// Depth against horizontal position as an SVG, a panel per course each
+/// scaled to fit, with depth increasing downwards like the sea
+pub fn svg(courses: &[Course]) -> String {
+    let height = MARGIN + courses.len() as f64 * (PANEL_HEIGHT + MARGIN);
+    let mut text = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"white\"/>\n",
+        w = WIDTH + 2.0 * MARGIN, h = height,
+    );
+    for (idx, course) in courses.iter().enumerate() {
+        let top = MARGIN + idx as f64 * (PANEL_HEIGHT + MARGIN);
+        let (min_x, max_x) = span(course.steps.iter().map(|step| step.horizontal));
+        let (min_y, max_y) = span(course.steps.iter().map(|step| step.depth));
+        // as f64 before subtracting, the span of an i64 may not fit in one
+        let x = |horizontal: i64| MARGIN + (horizontal as f64 - min_x as f64) / (max_x as f64 - min_x as f64) * WIDTH;
+        let y = |depth: i64| top + (depth as f64 - min_y as f64) / (max_y as f64 - min_y as f64) * PANEL_HEIGHT;
+
+        text.push_str(&format!("<text x=\"{}\" y=\"{}\" font-weight=\"bold\">{}</text>\n", MARGIN, top - 20.0, course.model));
+        text.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#eef6fb\" stroke=\"#999\"/>\n", MARGIN, top, WIDTH, PANEL_HEIGHT));
+        text.push_str(&format!("<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n", MARGIN - 5.0, top + 4.0, min_y));
+        text.push_str(&format!("<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n", MARGIN - 5.0, top + PANEL_HEIGHT, max_y));
+        text.push_str(&format!("<text x=\"{}\" y=\"{}\" text-anchor=\"end\">horizontal {}</text>\n", MARGIN + WIDTH, top + PANEL_HEIGHT + 15.0, max_x));
+        let points: Vec<String> = course.steps.iter().map(|step| format!("{:.1},{:.1}", x(step.horizontal), y(step.depth))).collect();
+        text.push_str(&format!("<polyline points=\"{}\" fill=\"none\" stroke=\"#1f5f8b\" stroke-width=\"1.5\"/>\n", points.join(" ")));
+    }
+    text.push_str("</svg>\n");
+    text
+}
+
+/// Smallest and largest of `values`, never equal so they can be divided by
+fn span(values: impl Iterator<Item = i64>) -> (i64, i64) {
+    let (min, max) = values.fold((i64::MAX, i64::MIN), |(min, max), value| (min.min(value), max.max(value)));
+    (min, max.max(min.saturating_add(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::navigation::{Aimed, Simple};
+
+    const EXAMPLE: [Command; 6] =
+        [Command::Forward(5), Command::Down(5), Command::Forward(8), Command::Up(3), Command::Down(8), Command::Forward(2)];
+
+    fn courses(commands: &[Command]) -> [Course; 2] {
+        [Course::trace("simple", Simple::default(), commands).unwrap(), Course::trace("aimed", Aimed::default(), commands).unwrap()]
+    }
+
+    #[test]
+    fn csv_rows() {
+        let text = csv(&courses(&EXAMPLE));
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows.len(), 1 + 2 * 7);
+        assert_eq!(rows[..3], ["model,step,horizontal,depth,aim", "simple,0,0,0,", "simple,1,5,0,"]);
+        assert_eq!(rows[7], "simple,6,15,10,");
+        assert_eq!(rows[8..], ["aimed,0,0,0,0", "aimed,1,5,0,0", "aimed,2,5,0,5", "aimed,3,13,40,5", "aimed,4,13,40,2", "aimed,5,13,40,10", "aimed,6,15,60,10"]);
+    }
+
+    #[test]
+    fn first_deeper_than() {
+        let [simple, aimed] = courses(&EXAMPLE);
+        assert_eq!(simple.first_deeper_than(-1).map(|step| step.step), Some(0));
+        assert_eq!(simple.first_deeper_than(4).map(|step| step.step), Some(2));
+        assert_eq!(aimed.first_deeper_than(39).map(|step| step.step), Some(3));
+        assert_eq!(aimed.first_deeper_than(60), None);
+    }
+
+    #[test]
+    fn deepest_is_the_first_of_equals() {
+        let [simple, aimed] = courses(&EXAMPLE);
+        // the simple model is at depth 10 after both steps 5 and 6
+        assert_eq!(*simple.deepest(), Step { step: 5, horizontal: 13, depth: 10, aim: None });
+        assert_eq!(*aimed.deepest(), Step { step: 6, horizontal: 15, depth: 60, aim: Some(10) });
+        assert_eq!(courses(&[Command::Forward(1)])[0].deepest().step, 0);
+    }
+
+    #[test]
+    fn flat_courses_still_scale() {
+        assert_eq!(span([0, 0, 0].into_iter()), (0, 1));
+        assert_eq!(span([-3, 7, 2].into_iter()), (-3, 7));
+        assert_eq!(span([i64::MIN, i64::MAX].into_iter()), (i64::MIN, i64::MAX));
+        for commands in [&[][..], &[Command::Forward(3), Command::Forward(4)], &[Command::Down(2), Command::Up(2)]] {
+            let text = svg(&courses(commands));
+            assert!(!text.contains("NaN") && !text.contains("inf"), "{}", text);
+        }
+    }
+}
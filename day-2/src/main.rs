@@ -1,45 +1,132 @@
-use std::io::{self, BufRead};
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use aoc::input::Input;
+use structopt::StructOpt;
+
+mod command;
+mod course;
+mod navigation;
+mod planner;
+mod script;
+
+use command::Command;
+use course::Course;
+use navigation::{Aimed, NavigationModel, Simple};
+
+// Commandline arguments
+#[derive(StructOpt)]
+struct Cli {
+    /// Read the course as a script with variables and loops
+    #[structopt(long)]
+    script: bool,
+    /// Write each model's step by step course as CSV
+    #[structopt(long, parse(from_os_str))]
+    csv: Option<PathBuf>,
+    /// Draw each model's depth profile as SVG
+    #[structopt(long, parse(from_os_str))]
+    svg: Option<PathBuf>,
+    /// Find the first step each model goes deeper than this
+    #[structopt(long, allow_hyphen_values = true)]
+    deeper_than: Option<i64>,
+    /// Find the greatest depth each model reaches
+    #[structopt(long)]
+    max_depth: bool,
+    /// Plan the fewest commands reaching this horizontal position and depth under the aim model
+    #[structopt(long, number_of_values = 2, value_names = &["horizontal", "depth"], allow_hyphen_values = true)]
+    plan: Option<Vec<i32>>,
+    /// Largest amount a planned command may move by
+    #[structopt(long)]
+    max_units: Option<i32>,
+    /// Show how long the plan is and whether it is the shortest
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: u8,
+    /// Only show errors
+    #[structopt(short, long)]
+    quiet: bool,
+}
+
+fn describe(step: &course::Step, commands: &[Command]) -> String {
+    let command = match step.step {
+        0 => "the start".to_string(),
+        n => format!("step {} ({})", n, commands[n - 1]),
+    };
+    format!("{}, horizontal {} depth {}", command, step.horizontal, step.depth)
+}
+
+fn print_position(model: Result<impl NavigationModel, navigation::Overflow>) {
+    let model = match model {
+        Ok(model) => model,
+        Err(e) => {
+            aoc::error!("{}", e);
+            process::exit(1);
+        }
+    };
+    // i128 holds the product of any two i64s
+    let product = model.horizontal() as i128 * model.depth() as i128;
+    println!("Horizontal: {}\tDepth: {}\tProduct: {}\r\n", model.horizontal(), model.depth(), product);
+}
 
 fn main() {
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
-
-    let mut horizontal: i32 = 0;
-    let mut depth: i32 = 0;
-
-    lines.iter().for_each(|line| {
-        let mut command = line.split_whitespace();
-        let direction = command.next().unwrap();
-        let magnitude = command.next().unwrap().parse::<i32>().unwrap();
-        match direction {
-            "forward" => horizontal += magnitude,
-            "up" => depth -= magnitude,
-            "down" => depth += magnitude,
-            _ =>  println!("Throw the switch Vern, she's pumping mud")
+    let args = Cli::from_args();
+    aoc::log::set_verbosity(args.verbose, args.quiet);
+    if let Some(target) = args.plan {
+        match planner::plan(target[0], target[1], args.max_units) {
+            Ok(plan) => {
+                plan.commands.iter().for_each(|command| println!("{}", command));
+                let shortest = if plan.shortest { "shortest" } else { "fewest found, the search gave up" };
+                aoc::info!("{} commands reach horizontal {} depth {} ({})", plan.commands.len(), target[0], target[1], shortest);
+            }
+            Err(e) => {
+                aoc::error!("{}", e);
+                process::exit(1);
+            }
         }
-    });
+        return;
+    }
 
-    println!("Part 1\r\n{}", "-".repeat(10));
-    println!("Horizontal: {}\tDepth: {}\tProduct: {}\r\n", horizontal, depth, horizontal * depth);
-
-
-    let mut horizontal: i32 = 0;
-    let mut depth: i32 = 0;
-    let mut aim: i32 = 0;
-
-    lines.iter().for_each(|line| {
-        let mut command = line.split_whitespace();
-        let direction = command.next().unwrap();
-        let magnitude = command.next().unwrap().parse::<i32>().unwrap();
-        match direction {
-            "forward" => {horizontal += magnitude;  depth += aim * magnitude},
-            "up" => aim -= magnitude,
-            "down" => aim += magnitude,
-            _ =>  println!("Throw the switch Vern, she's pumping mud")
+    let input = Input::from_stdin();
+    let commands = match args.script {
+        true => script::expand(input.text()).map_err(|e| e.to_string()),
+        false => command::parse_commands(input.text()).map_err(|e| e.to_string()),
+    };
+    let commands = match commands {
+        Ok(commands) => commands,
+        Err(e) => {
+            aoc::error!("{}", e);
+            process::exit(1);
         }
-    });
+    };
+
+    println!("Part 1\r\n{}", "-".repeat(10));
+    print_position(Simple::default().navigate(&commands));
 
     println!("Part 2\r\n{}", "-".repeat(10));
-    println!("Horizontal: {}\tDepth: {}\tProduct: {}\r\n", horizontal, depth, horizontal * depth);
+    print_position(Aimed::default().navigate(&commands));
 
+    if args.csv.is_none() && args.svg.is_none() && args.deeper_than.is_none() && !args.max_depth {
+        return;
+    }
+    // both models got through every command above, so tracing them can't overflow
+    let courses = [Course::trace("simple", Simple::default(), &commands).unwrap(), Course::trace("aimed", Aimed::default(), &commands).unwrap()];
+    for course in courses.iter() {
+        if let Some(depth) = args.deeper_than {
+            match course.first_deeper_than(depth) {
+                Some(step) => println!("{}: deeper than {} at {}", course.model, depth, describe(step, &commands)),
+                None => println!("{}: never deeper than {}", course.model, depth),
+            }
+        }
+        if args.max_depth {
+            println!("{}: deepest at {}", course.model, describe(course.deepest(), &commands));
+        }
+    }
+    let exports = [(&args.csv, course::csv as fn(&[Course]) -> String), (&args.svg, course::svg)];
+    for (path, export) in exports {
+        if let Some(path) = path {
+            if let Err(e) = fs::write(path, export(&courses)) {
+                aoc::error!("couldn't write {}: {}", path.display(), e);
+                process::exit(1);
+            }
+        }
+    }
 }
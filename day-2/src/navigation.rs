@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crate::command::Command;
+
+/// How the submarine moves for each command, so models share one loop
+pub trait NavigationModel {
+    /// Moves for one command, `None` if the position no longer fits
+    fn apply(&mut self, command: Command) -> Option<()>;
+    fn horizontal(&self) -> i64;
+    fn depth(&self) -> i64;
+
+    /// Where the submarine is pointing, for models that keep an aim
+    fn aim(&self) -> Option<i64> {
+        None
+    }
+
+    /// Follows every command in turn
+    fn navigate(mut self, commands: &[Command]) -> Result<Self, Overflow>
+    where
+        Self: Sized,
+    {
+        for (idx, &command) in commands.iter().enumerate() {
+            self.apply(command).ok_or(Overflow { step: idx + 1, command })?;
+        }
+        Ok(self)
+    }
+}
+
+/// A command taking the submarine further than an `i64` can count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub step: usize,
+    pub command: Command,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "step {} ({}) goes further than can be counted", self.step, self.command)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Part 1, `down` and `up` change the depth directly
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Simple {
+    horizontal: i64,
+    depth: i64,
+}
+
+impl NavigationModel for Simple {
+    fn apply(&mut self, command: Command) -> Option<()> {
+        match command {
+            Command::Forward(units) => self.horizontal = self.horizontal.checked_add(units.into())?,
+            Command::Down(units) => self.depth = self.depth.checked_add(units.into())?,
+            Command::Up(units) => self.depth = self.depth.checked_sub(units.into())?,
+        }
+        Some(())
+    }
+
+    fn horizontal(&self) -> i64 {
+        self.horizontal
+    }
+
+    fn depth(&self) -> i64 {
+        self.depth
+    }
+}
+
+/// Part 2, `down` and `up` change the aim and `forward` dives along it
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Aimed {
+    horizontal: i64,
+    depth: i64,
+    aim: i64,
+}
+
+impl NavigationModel for Aimed {
+    fn apply(&mut self, command: Command) -> Option<()> {
+        match command {
+            Command::Forward(units) => {
+                self.horizontal = self.horizontal.checked_add(units.into())?;
+                self.depth = self.depth.checked_add(self.aim.checked_mul(units.into())?)?;
+            }
+            Command::Down(units) => self.aim = self.aim.checked_add(units.into())?,
+            Command::Up(units) => self.aim = self.aim.checked_sub(units.into())?,
+        }
+        Some(())
+    }
+
+    fn horizontal(&self) -> i64 {
+        self.horizontal
+    }
+
+    fn depth(&self) -> i64 {
+        self.depth
+    }
+
+    fn aim(&self) -> Option<i64> {
+        Some(self.aim)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [Command; 6] =
+        [Command::Forward(5), Command::Down(5), Command::Forward(8), Command::Up(3), Command::Down(8), Command::Forward(2)];
+
+    #[test]
+    fn example() {
+        let simple = Simple::default().navigate(&EXAMPLE).unwrap();
+        assert_eq!((simple.horizontal(), simple.depth(), simple.aim()), (15, 10, None));
+        assert_eq!(simple.horizontal() * simple.depth(), 150);
+
+        let aimed = Aimed::default().navigate(&EXAMPLE).unwrap();
+        assert_eq!((aimed.horizontal(), aimed.depth(), aimed.aim()), (15, 60, Some(10)));
+        assert_eq!(aimed.horizontal() * aimed.depth(), 900);
+    }
+
+    #[test]
+    fn overflow_names_the_step() {
+        let far = Command::Forward(i32::MAX);
+        // each forward dives by (2^31 - 1)^2, just under 2^62, so the third goes past i64::MAX
+        let overflow = Aimed::default().navigate(&[Command::Down(i32::MAX), far, far, far, far]).unwrap_err();
+        assert_eq!(overflow, Overflow { step: 4, command: far });
+        assert_eq!(overflow.to_string(), "step 4 (forward 2147483647) goes further than can be counted");
+
+        let near_floor = Simple { horizontal: 0, depth: i64::MIN + 2 };
+        let overflow = near_floor.navigate(&[Command::Up(1), Command::Down(1), Command::Up(2), Command::Up(1)]).unwrap_err();
+        assert_eq!(overflow, Overflow { step: 4, command: Command::Up(1) });
+    }
+}
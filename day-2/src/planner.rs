@@ -0,0 +1,293 @@
+//! Plans a course reaching a target under the aim model, with as few
+//! commands as possible.
+//!
+//! An aim change of `t` made with `r` still to go forward adds `t * r` to the
+//! final depth, so a plan comes down to the points along the way where the
+//! aim changes and by how much. Without a limit on magnitudes three commands
+//! always do (`forward h-1`, `down d`, `forward 1`). With one, plans are
+//! searched by iterative deepening from a lower bound on their length.
+
+use crate::command::Command;
+use crate::navigation::{Aimed, NavigationModel};
+use crate::script;
+
+/// Positions the search may visit before settling for the best plan so far
+const NODE_LIMIT: usize = 20_000_000;
+
+pub struct Plan {
+    pub commands: Vec<Command>,
+    /// Whether no shorter plan exists, false when the search gave up early
+    pub shortest: bool,
+}
+
+/// A shortest course from the surface to `horizontal` and `depth`, every
+/// command moving at least 1 and at most `max_units` when given
+pub fn plan(horizontal: i32, depth: i32, max_units: Option<i32>) -> Result<Plan, String> {
+    if horizontal < 0 {
+        return Err(format!("can't reach horizontal {}, forward only goes forwards", horizontal));
+    }
+    if max_units.is_some_and(|units| units < 1) {
+        return Err("commands move at least 1 unit".to_string());
+    }
+    if horizontal == 0 && depth != 0 {
+        return Err(format!("depth only changes moving forward, so depth {} can't be reached at horizontal 0", depth));
+    }
+    let (h, d) = (horizontal as i64, depth as i64);
+    // a command moves at most i32::MAX even without a limit
+    let units = max_units.unwrap_or(i32::MAX) as i64;
+
+    // three commands always do with magnitudes up to the larger of the two
+    let (cuts, shortest) = if units >= h.max(d.abs()) {
+        let cuts = match d {
+            0 => vec!(),
+            _ if d % h == 0 => vec![(h, d / h)],
+            _ => vec![(1, d)],
+        };
+        (cuts, true)
+    } else {
+        search(h, d, units)
+    };
+
+    // checked before writing them out, as a plan could be far longer than a script may be
+    let length = cost(h, &cuts, units);
+    if length > script::MAX_COMMANDS as i64 {
+        return Err(format!("the shortest course found is {} commands, more than the {} a course may have", length, script::MAX_COMMANDS));
+    }
+    let commands = commands(h, &cuts, units);
+    verify(&commands, horizontal, depth, max_units)?;
+    Ok(Plan { commands, shortest })
+}
+
+fn ceil_div(value: i64, divisor: i64) -> i64 {
+    value / divisor + (value % divisor != 0) as i64
+}
+
+/// Aim changes `(to go, change)` in the order they are made
+type Cuts = Vec<(i64, i64)>;
+
+/// Commands for the aim changes, with the forwards between them split into
+/// as few moves of at most `units` as will do
+fn commands(h: i64, cuts: &[(i64, i64)], units: i64) -> Vec<Command> {
+    let mut commands = vec!();
+    let split = |commands: &mut Vec<Command>, amount: i64, command: fn(i32) -> Command| {
+        let parts = ceil_div(amount, units);
+        for part in 0..parts {
+            // spread evenly, the first `amount % parts` get one more
+            commands.push(command((amount / parts + (part < amount % parts) as i64) as i32));
+        }
+    };
+    let mut to_go = h;
+    for &(at, change) in cuts {
+        split(&mut commands, to_go - at, Command::Forward);
+        split(&mut commands, change.abs(), if change > 0 { Command::Down } else { Command::Up });
+        to_go = at;
+    }
+    split(&mut commands, to_go, Command::Forward);
+    commands
+}
+
+/// Runs the plan back through the script interpreter and the aim model
+fn verify(commands: &[Command], horizontal: i32, depth: i32, max_units: Option<i32>) -> Result<(), String> {
+    let text: Vec<String> = commands.iter().map(|command| command.to_string()).collect();
+    let replayed = script::expand(&text.join("\n")).map_err(|e| format!("planned an unreadable course: {}", e))?;
+    let aimed = Aimed::default().navigate(&replayed).map_err(|e| format!("planned a course that overflows: {}", e))?;
+    let in_range = replayed.iter().all(|&(Command::Forward(units) | Command::Down(units) | Command::Up(units))| {
+        units >= 1 && max_units.is_none_or(|max| units <= max)
+    });
+    if replayed.as_slice() != commands || !in_range || (aimed.horizontal(), aimed.depth()) != (horizontal.into(), depth.into()) {
+        return Err(format!("planned a course ending at horizontal {} depth {} instead", aimed.horizontal(), aimed.depth()));
+    }
+    Ok(())
+}
+
+/// Iterative deepening between a lower bound and the best simple plan
+fn search(h: i64, d: i64, units: i64) -> (Cuts, bool) {
+    let lower = ceil_div(h, units) + ceil_div(d.abs(), units * h);
+    let (mut best_cost, mut best) = simple_plan(h, d, units);
+    let mut search = Search { units, nodes: 0, cuts: vec!() };
+    for limit in lower..best_cost {
+        match search.dfs(h, true, d, 0, limit) {
+            Ok(true) => {
+                best = search.cuts.clone();
+                best_cost = limit;
+                break;
+            }
+            Ok(false) => (),
+            Err(()) => return (best, false),
+        }
+    }
+    aoc::debug!("planned {} commands (at least {}) after {} positions", best_cost, lower, search.nodes);
+    (best, true)
+}
+
+/// What a plan costs in commands
+fn cost(h: i64, cuts: &[(i64, i64)], units: i64) -> i64 {
+    let mut to_go = h;
+    let mut cost = 0;
+    for &(at, change) in cuts {
+        cost += ceil_div(to_go - at, units) + ceil_div(change.abs(), units);
+        to_go = at;
+    }
+    cost + ceil_div(to_go, units)
+}
+
+/// The best plan with at most two aim changes, the first right at the
+/// start, to bound the search
+fn simple_plan(h: i64, d: i64, units: i64) -> (i64, Cuts) {
+    // the remainder from a whole number of `h` is made up by one later change
+    let mut plans: Vec<Cuts> = vec![vec![(1, d)]];
+    let quotient = d / h;
+    for first in (quotient - 2)..=(quotient + 2) {
+        let rest = d - first * h;
+        let start = if first == 0 { vec!() } else { vec![(h, first)] };
+        if rest == 0 {
+            plans.push(start);
+            continue;
+        }
+        for at in divisors(rest.abs()).filter(|&at| at < h) {
+            plans.push(start.iter().copied().chain([(at, rest / at)]).collect());
+        }
+    }
+    plans.into_iter().map(|cuts| (cost(h, &cuts, units), cuts)).min_by_key(|(cost, _)| *cost).unwrap()
+}
+
+/// Every divisor of `n`, found in pairs up to its square root
+fn divisors(n: i64) -> impl Iterator<Item = i64> {
+    (1..).take_while(move |&small| small * small <= n).filter(move |&small| n % small == 0).flat_map(move |small| {
+        let large = n / small;
+        [Some(small), Some(large).filter(|&large| large != small)].into_iter().flatten()
+    })
+}
+
+struct Search {
+    units: i64,
+    nodes: usize,
+    /// The aim changes of the plan being tried
+    cuts: Cuts,
+}
+
+impl Search {
+    /// Whether the remaining `depth` can be made up with `to_go` forward
+    /// left in at most `limit` commands, `cost` already spent. `Err` once
+    /// the search has gone on too long.
+    fn dfs(&mut self, to_go: i64, first: bool, depth: i64, cost: i64, limit: i64) -> Result<bool, ()> {
+        self.visit()?;
+        let units = self.units;
+        if depth == 0 {
+            return Ok(cost + ceil_div(to_go, units) <= limit);
+        }
+        // the next change can be where we are only before the first forward
+        let nearest = if first { to_go } else { to_go - 1 };
+        let ats: Box<dyn Iterator<Item = i64>> = match limit - cost - ceil_div(to_go, units) {
+            // with a single command to spare, one last change has to make up
+            // the depth exactly, so it can only be made a divisor away
+            1 => {
+                let mut ats: Vec<i64> = divisors(depth.abs()).filter(|&at| at <= nearest).collect();
+                ats.sort_unstable_by(|a, b| b.cmp(a));
+                Box::new(ats.into_iter())
+            }
+            _ => Box::new((1..=nearest).rev()),
+        };
+        for at in ats {
+            self.visit()?;
+            // further on every change is worth less, so more are needed
+            if cost + ceil_div(to_go, units) + ceil_div(depth.abs(), units * at) > limit {
+                break;
+            }
+            let spent = cost + ceil_div(to_go - at, units);
+            let budget = limit - spent - ceil_div(at, units);
+            for changes in 1..=budget {
+                let rest = (budget - changes) as i128;
+                let (least, most) = ((changes - 1) * units + 1, changes.saturating_mul(units));
+                // what the changes after this one could still make up
+                let slack = rest * units as i128 * (at - 1) as i128;
+                let low = div_floor(depth as i128 - slack + at as i128 - 1, at as i128);
+                let high = div_floor(depth as i128 + slack, at as i128);
+                for change in low.max(-(most as i128))..=high.min(most as i128) {
+                    let change = change as i64;
+                    if change.abs() < least {
+                        continue;
+                    }
+                    self.cuts.push((at, change));
+                    if self.dfs(at, false, depth - change * at, spent + changes, limit)? {
+                        return Ok(true);
+                    }
+                    self.cuts.pop();
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl Search {
+    /// Counts a position tried, `Err` once the search has gone on too long
+    fn visit(&mut self) -> Result<(), ()> {
+        self.nodes += 1;
+        match self.nodes > NODE_LIMIT {
+            true => Err(()),
+            false => Ok(()),
+        }
+    }
+}
+
+fn div_floor(value: i128, divisor: i128) -> i128 {
+    value.div_euclid(divisor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashSet, VecDeque};
+
+    /// Fewest commands to `horizontal` and `depth` by breadth first search,
+    /// with the aim and depth kept within bounds far beyond what a shortest
+    /// course needs
+    fn fewest(horizontal: i32, depth: i32, units: i32) -> usize {
+        let (aim_bound, depth_bound) = (depth.abs() + 2 * units, 2 * depth.abs() + 2 * horizontal * units);
+        let mut seen = HashSet::from([(0, 0, 0)]);
+        let mut queue = VecDeque::from([((0, 0, 0), 0)]);
+        while let Some(((h, d, aim), length)) = queue.pop_front() {
+            if (h, d) == (horizontal, depth) {
+                return length;
+            }
+            for units in 1..=units {
+                for next in [(h + units, d + aim * units, aim), (h, d, aim + units), (h, d, aim - units)] {
+                    if next.0 <= horizontal && next.1.abs() <= depth_bound && next.2.abs() <= aim_bound && seen.insert(next) {
+                        queue.push_back((next, length + 1));
+                    }
+                }
+            }
+        }
+        panic!("no course to horizontal {} depth {}", horizontal, depth);
+    }
+
+    #[test]
+    fn plans_are_as_short_as_a_search_finds() {
+        for horizontal in 1..=4 {
+            for depth in -10..=10 {
+                for max_units in [Some(1), Some(2), Some(3), None] {
+                    let planned = plan(horizontal, depth, max_units).unwrap();
+                    let units = max_units.unwrap_or(horizontal.max(depth.abs()));
+                    assert_eq!(planned.commands.len(), fewest(horizontal, depth, units), "horizontal {} depth {} max {:?}", horizontal, depth, max_units);
+                    assert!(planned.shortest);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn plans_without_a_limit_fit_in_commands() {
+        let planned = plan(1, i32::MIN, None).unwrap();
+        assert_eq!(planned.commands, vec![Command::Up(1073741824), Command::Up(1073741824), Command::Forward(1)]);
+        assert_eq!(plan(2000000000, 7, Some(100000000)).unwrap().commands.len(), 22);
+    }
+
+    #[test]
+    fn unreachable_targets() {
+        assert!(plan(-1, 0, None).is_err());
+        assert!(plan(0, 5, None).is_err());
+        assert!(plan(5, 5, Some(0)).is_err());
+        assert!(plan(2000000000, i32::MIN, Some(7)).is_err());
+    }
+}
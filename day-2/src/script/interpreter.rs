@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use super::parser::{Expr, ExprKind, Operator, Statement};
+use super::{Position, ScriptError};
+use crate::command::Command;
+
+/// More commands than any course needs, so a runaway `repeat` stops with an
+/// error instead of eating all the memory
+pub const MAX_COMMANDS: usize = 10_000_000;
+/// Likewise for statements run and rounds of `repeat`, which catches loops
+/// that only set variables or do nothing at all
+const MAX_STEPS: usize = 100_000_000;
+
+/// Runs the statements, collecting the commands they steer by
+pub fn run(program: &[Statement]) -> Result<Vec<Command>, ScriptError> {
+    let mut interpreter = Interpreter { variables: HashMap::new(), commands: vec!(), steps: 0 };
+    interpreter.statements(program)?;
+    Ok(interpreter.commands)
+}
+
+struct Interpreter {
+    variables: HashMap<String, i32>,
+    commands: Vec<Command>,
+    steps: usize,
+}
+
+impl Interpreter {
+    fn statements(&mut self, statements: &[Statement]) -> Result<(), ScriptError> {
+        for statement in statements {
+            self.step(statement.at())?;
+            match statement {
+                Statement::Let(name, value) => {
+                    let value = self.evaluate(value)?;
+                    self.variables.insert(name.clone(), value);
+                }
+                Statement::Repeat(count, body) => {
+                    let times = self.evaluate(count)?;
+                    if times < 0 {
+                        return Err(ScriptError::new(count.at, format!("can't repeat {} times", times)));
+                    }
+                    for _ in 0..times {
+                        // counted too, or a loop around nothing would never stop
+                        self.step(count.at)?;
+                        self.statements(body)?;
+                    }
+                }
+                Statement::Move(command, units) => {
+                    if self.commands.len() == MAX_COMMANDS {
+                        return Err(ScriptError::new(units.at, format!("the course is longer than {} commands", MAX_COMMANDS)));
+                    }
+                    let units = self.evaluate(units)?;
+                    self.commands.push(command(units));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn step(&mut self, at: Position) -> Result<(), ScriptError> {
+        self.steps += 1;
+        match self.steps > MAX_STEPS {
+            true => Err(ScriptError::new(at, format!("the script runs for more than {} steps", MAX_STEPS))),
+            false => Ok(()),
+        }
+    }
+
+    fn evaluate(&self, expr: &Expr) -> Result<i32, ScriptError> {
+        let overflow = || ScriptError::new(expr.at, "arithmetic overflow");
+        match &expr.kind {
+            ExprKind::Number(number) => Ok(*number),
+            ExprKind::Variable(name) => self.variables.get(name).copied().ok_or_else(|| ScriptError::new(expr.at, format!("{} isn't set", name))),
+            ExprKind::Negate(inner) => self.evaluate(inner)?.checked_neg().ok_or_else(overflow),
+            ExprKind::Binary(left, operator, right) => {
+                let (left, right) = (self.evaluate(left)?, self.evaluate(right)?);
+                match operator {
+                    Operator::Add => left.checked_add(right).ok_or_else(overflow),
+                    Operator::Subtract => left.checked_sub(right).ok_or_else(overflow),
+                    Operator::Multiply => left.checked_mul(right).ok_or_else(overflow),
+                    Operator::Divide if right == 0 => Err(ScriptError::new(expr.at, "division by zero")),
+                    Operator::Divide => left.checked_div(right).ok_or_else(overflow),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use super::{Position, ScriptError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Number(i32),
+    Name(String),
+    Let,
+    Repeat,
+    Forward,
+    Down,
+    Up,
+    Equals,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    OpenParen,
+    CloseParen,
+    OpenBrace,
+    CloseBrace,
+    /// `;` or a line break
+    End,
+    Eof,
+}
+
+impl Token {
+    /// How the token reads in error messages
+    pub fn describe(&self) -> String {
+        match self {
+            Token::Number(number) => number.to_string(),
+            Token::Name(name) => format!("{:?}", name),
+            Token::Let => "\"let\"".to_string(),
+            Token::Repeat => "\"repeat\"".to_string(),
+            Token::Forward => "\"forward\"".to_string(),
+            Token::Down => "\"down\"".to_string(),
+            Token::Up => "\"up\"".to_string(),
+            Token::Equals => "\"=\"".to_string(),
+            Token::Plus => "\"+\"".to_string(),
+            Token::Minus => "\"-\"".to_string(),
+            Token::Star => "\"*\"".to_string(),
+            Token::Slash => "\"/\"".to_string(),
+            Token::OpenParen => "\"(\"".to_string(),
+            Token::CloseParen => "\")\"".to_string(),
+            Token::OpenBrace => "\"{\"".to_string(),
+            Token::CloseBrace => "\"}\"".to_string(),
+            Token::End => "end of statement".to_string(),
+            Token::Eof => "end of script".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Spanned {
+    pub token: Token,
+    pub at: Position,
+}
+
+/// Splits a script into tokens, skipping spaces and `#` comments, and
+/// always ending with `Token::Eof`
+pub fn tokenize(script: &str) -> Result<Vec<Spanned>, ScriptError> {
+    let mut tokens = vec!();
+    let mut chars = script.chars().peekable();
+    let mut at = Position { line: 1, column: 1 };
+
+    while let Some(&c) = chars.peek() {
+        let start = at;
+        let mut take = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+            at.column += 1;
+            chars.next()
+        };
+        let token = match c {
+            '\n' => {
+                chars.next();
+                at = Position { line: at.line + 1, column: 1 };
+                Token::End
+            }
+            '#' => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    take(&mut chars);
+                }
+                continue;
+            }
+            c if c.is_whitespace() => {
+                take(&mut chars);
+                continue;
+            }
+            c if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(&digit) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    digits.push(digit);
+                    take(&mut chars);
+                }
+                Token::Number(digits.parse().map_err(|_| ScriptError::new(start, format!("{} is too large", digits)))?)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut word = String::new();
+                while let Some(&letter) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                    word.push(letter);
+                    take(&mut chars);
+                }
+                match word.as_str() {
+                    "let" => Token::Let,
+                    "repeat" => Token::Repeat,
+                    "forward" => Token::Forward,
+                    "down" => Token::Down,
+                    "up" => Token::Up,
+                    _ => Token::Name(word),
+                }
+            }
+            _ => {
+                take(&mut chars);
+                match c {
+                    ';' => Token::End,
+                    '=' => Token::Equals,
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '*' => Token::Star,
+                    '/' => Token::Slash,
+                    '(' => Token::OpenParen,
+                    ')' => Token::CloseParen,
+                    '{' => Token::OpenBrace,
+                    '}' => Token::CloseBrace,
+                    _ => return Err(ScriptError::new(start, format!("unexpected {:?}", c))),
+                }
+            }
+        };
+        tokens.push(Spanned { token, at: start });
+    }
+    tokens.push(Spanned { token: Token::Eof, at });
+    Ok(tokens)
+}
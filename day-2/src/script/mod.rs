@@ -0,0 +1,112 @@
+//! A small language for writing courses, expanded into the same commands
+//! as a puzzle input:
+//!
+//! ```text
+//! # zig zag down to the trench
+//! let step = 2
+//! repeat 10 {
+//!     forward 5; down step
+//!     let step = step * 2
+//! }
+//! up 3
+//! ```
+//!
+//! Statements end at a `;` or a line break. `let` sets a variable, there is
+//! a single set of them so a `let` inside `repeat` carries over between
+//! rounds. Amounts are integer expressions with `+ - * /` and parentheses.
+//! A plain list of commands is a script too.
+
+use std::fmt;
+
+use crate::command::Command;
+
+mod interpreter;
+mod lexer;
+mod parser;
+
+pub use interpreter::MAX_COMMANDS;
+
+/// Where in the script something is, both counted from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A syntax or runtime error, positioned within the script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptError {
+    pub at: Position,
+    pub message: String,
+}
+
+impl ScriptError {
+    fn new(at: Position, message: impl Into<String>) -> Self {
+        ScriptError { at, message: message.into() }
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.at.line, self.at.column, self.message)
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// Runs `script`, returning the commands it steers by
+pub fn expand(script: &str) -> Result<Vec<Command>, ScriptError> {
+    let tokens = lexer::tokenize(script)?;
+    let program = parser::parse(&tokens)?;
+    interpreter::run(&program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Where `script` fails, and why
+    fn error(script: &str) -> (usize, usize, String) {
+        let error = expand(script).unwrap_err();
+        (error.at.line, error.at.column, error.message)
+    }
+
+    #[test]
+    fn expands_loops_and_variables() {
+        let commands = expand("let x = 2\nrepeat 2 { forward x; let x = x * 3 }; down -(1 - 4) / 2").unwrap();
+        assert_eq!(commands, vec![Command::Forward(2), Command::Forward(6), Command::Down(1)]);
+        assert_eq!(expand("forward 5\ndown 5\n").unwrap(), vec![Command::Forward(5), Command::Down(5)]);
+    }
+
+    #[test]
+    fn syntax_errors_are_positioned() {
+        assert_eq!(error("forward 1\n  down ?"), (2, 8, "unexpected '?'".to_string()));
+        assert_eq!(error("forward 1\nlet = 3"), (2, 5, "expected a variable name, found \"=\"".to_string()));
+        assert_eq!(error("repeat 3 {\n  up 1\n"), (3, 1, "expected \"}\", found end of script".to_string()));
+        assert_eq!(error("forward 1 2"), (1, 11, "expected \";\" or a new line, found 2".to_string()));
+        assert_eq!(error("down 99999999999"), (1, 6, "99999999999 is too large".to_string()));
+    }
+
+    #[test]
+    fn runtime_errors_are_positioned() {
+        assert_eq!(error("let a = 1\nforward a + b"), (2, 13, "b isn't set".to_string()));
+        assert_eq!(error("down 4 / (2 - 2)"), (1, 8, "division by zero".to_string()));
+        assert_eq!(error("up 2147483647 + 1"), (1, 15, "arithmetic overflow".to_string()));
+        assert_eq!(error("repeat 0 - 1 { up 1 }"), (1, 10, "can't repeat -1 times".to_string()));
+    }
+
+    #[test]
+    fn runaway_scripts_stop() {
+        let (line, column, message) = error("\n  repeat 2000000000 { repeat 2000000000 { } }");
+        assert_eq!((line, column), (2, 30));
+        assert!(message.starts_with("the script runs for more than"));
+
+        let deep = format!("forward {}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        let (line, column, message) = error(&deep);
+        assert_eq!((line, column), (1, 9 + parser::MAX_NESTING));
+        assert!(message.starts_with("nested more than"));
+
+        let long = format!("forward {}", vec!["1"; 100_000].join("+"));
+        assert!(error(&long).2.ends_with("operators deep"));
+    }
+}
@@ -0,0 +1,250 @@
+use super::lexer::{Spanned, Token};
+use super::{Position, ScriptError};
+use crate::command::Command;
+
+/// Deepest the brackets, negations, `repeat`s and operators may nest, as
+/// parsing and running both recurse through them
+pub const MAX_NESTING: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExprKind {
+    Number(i32),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary(Box<Expr>, Operator, Box<Expr>),
+}
+
+/// An expression and where it starts, or for binary ones where the operator is
+#[derive(Debug, Clone)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub at: Position,
+    /// Levels of expression from this one down, itself included
+    pub depth: usize,
+}
+
+impl Expr {
+    fn new(kind: ExprKind, at: Position) -> Self {
+        let depth = match &kind {
+            ExprKind::Negate(inner) => inner.depth + 1,
+            ExprKind::Binary(left, _, right) => left.depth.max(right.depth) + 1,
+            ExprKind::Number(_) | ExprKind::Variable(_) => 1,
+        };
+        Expr { kind, at, depth }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Statement {
+    Let(String, Expr),
+    Repeat(Expr, Vec<Statement>),
+    Move(fn(i32) -> Command, Expr),
+}
+
+impl Statement {
+    /// Roughly where the statement is, the start of its expression
+    pub fn at(&self) -> Position {
+        match self {
+            Statement::Let(_, value) => value.at,
+            Statement::Repeat(count, _) => count.at,
+            Statement::Move(_, units) => units.at,
+        }
+    }
+}
+
+/// Builds the statements of a whole script from its tokens
+pub fn parse(tokens: &[Spanned]) -> Result<Vec<Statement>, ScriptError> {
+    let mut parser = Parser { tokens, next: 0, nesting: 0 };
+    let statements = parser.statements()?;
+    match parser.peek() {
+        Token::Eof => Ok(statements),
+        _ => Err(parser.expected("a statement")),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Spanned],
+    next: usize,
+    /// Brackets, negations and `repeat`s open around the next token
+    nesting: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &'a Token {
+        &self.tokens[self.next].token
+    }
+
+    fn at(&self) -> Position {
+        self.tokens[self.next].at
+    }
+
+    fn advance(&mut self) -> &'a Token {
+        let token = &self.tokens[self.next].token;
+        if *token != Token::Eof {
+            self.next += 1;
+        }
+        token
+    }
+
+    fn expected(&self, what: &str) -> ScriptError {
+        ScriptError::new(self.at(), format!("expected {}, found {}", what, self.peek().describe()))
+    }
+
+    fn expect(&mut self, token: Token, what: &str) -> Result<(), ScriptError> {
+        match *self.peek() == token {
+            true => {
+                self.advance();
+                Ok(())
+            }
+            false => Err(self.expected(what)),
+        }
+    }
+
+    /// Parses one level further in, unless that is too deep
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, ScriptError>) -> Result<T, ScriptError> {
+        if self.nesting == MAX_NESTING {
+            return Err(ScriptError::new(self.at(), format!("nested more than {} deep", MAX_NESTING)));
+        }
+        self.nesting += 1;
+        let parsed = parse(self);
+        self.nesting -= 1;
+        parsed
+    }
+
+    /// `left operator right`, unless that chains operators too deep
+    fn binary(&self, left: Expr, operator: Operator, right: Expr, at: Position) -> Result<Expr, ScriptError> {
+        let expr = Expr::new(ExprKind::Binary(Box::new(left), operator, Box::new(right)), at);
+        match expr.depth > MAX_NESTING {
+            true => Err(ScriptError::new(at, format!("more than {} operators deep", MAX_NESTING))),
+            false => Ok(expr),
+        }
+    }
+
+    fn skip_ends(&mut self) {
+        while *self.peek() == Token::End {
+            self.advance();
+        }
+    }
+
+    /// Statements up to a `}` or the end of the script, whichever comes first
+    fn statements(&mut self) -> Result<Vec<Statement>, ScriptError> {
+        let mut statements = vec!();
+        self.skip_ends();
+        while !matches!(self.peek(), Token::CloseBrace | Token::Eof) {
+            statements.push(self.statement()?);
+            // a statement ends at a `;`, a line break, or a closing brace on its line
+            match self.peek() {
+                Token::End => self.skip_ends(),
+                Token::CloseBrace | Token::Eof => (),
+                _ => return Err(self.expected("\";\" or a new line")),
+            }
+        }
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Statement, ScriptError> {
+        match self.peek() {
+            Token::Let => {
+                self.advance();
+                let name = match self.peek() {
+                    Token::Name(name) => name.clone(),
+                    _ => return Err(self.expected("a variable name")),
+                };
+                self.advance();
+                self.expect(Token::Equals, "\"=\"")?;
+                Ok(Statement::Let(name, self.expression()?))
+            }
+            Token::Repeat => {
+                self.advance();
+                let count = self.expression()?;
+                let body = self.nested(|parser| {
+                    parser.expect(Token::OpenBrace, "\"{\"")?;
+                    let body = parser.statements()?;
+                    parser.expect(Token::CloseBrace, "\"}\"")?;
+                    Ok(body)
+                })?;
+                Ok(Statement::Repeat(count, body))
+            }
+            Token::Forward => self.movement(Command::Forward),
+            Token::Down => self.movement(Command::Down),
+            Token::Up => self.movement(Command::Up),
+            _ => Err(self.expected("forward, down, up, let or repeat")),
+        }
+    }
+
+    fn movement(&mut self, command: fn(i32) -> Command) -> Result<Statement, ScriptError> {
+        self.advance();
+        Ok(Statement::Move(command, self.expression()?))
+    }
+
+    /// Terms added or subtracted, left to right
+    fn expression(&mut self) -> Result<Expr, ScriptError> {
+        let mut left = self.term()?;
+        loop {
+            let operator = match self.peek() {
+                Token::Plus => Operator::Add,
+                Token::Minus => Operator::Subtract,
+                _ => return Ok(left),
+            };
+            let at = self.at();
+            self.advance();
+            let right = self.term()?;
+            left = self.binary(left, operator, right, at)?;
+        }
+    }
+
+    /// Factors multiplied or divided, left to right
+    fn term(&mut self) -> Result<Expr, ScriptError> {
+        let mut left = self.factor()?;
+        loop {
+            let operator = match self.peek() {
+                Token::Star => Operator::Multiply,
+                Token::Slash => Operator::Divide,
+                _ => return Ok(left),
+            };
+            let at = self.at();
+            self.advance();
+            let right = self.factor()?;
+            left = self.binary(left, operator, right, at)?;
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expr, ScriptError> {
+        let at = self.at();
+        let kind = match self.peek() {
+            Token::Number(number) => {
+                self.advance();
+                ExprKind::Number(*number)
+            }
+            Token::Name(name) => {
+                self.advance();
+                ExprKind::Variable(name.clone())
+            }
+            Token::Minus => {
+                let inner = self.nested(|parser| {
+                    parser.advance();
+                    parser.factor()
+                })?;
+                ExprKind::Negate(Box::new(inner))
+            }
+            Token::OpenParen => {
+                return self.nested(|parser| {
+                    parser.advance();
+                    let inner = parser.expression()?;
+                    parser.expect(Token::CloseParen, "\")\"")?;
+                    Ok(inner)
+                });
+            }
+            _ => return Err(self.expected("a number or variable")),
+        };
+        Ok(Expr::new(kind, at))
+    }
+}
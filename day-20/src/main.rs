@@ -1,10 +1,12 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
+use aoc::bitset::DynBitSet;
+use aoc::checkpoint::{Checkpoints, State};
+use aoc::input::Input;
 
 struct Image {
     rows: usize,
     cols: usize,
-    image: Vec<bool>
+    image: DynBitSet
 }
 
 impl std::fmt::Display for Image {
@@ -12,7 +14,7 @@ impl std::fmt::Display for Image {
         let mut str: String = String::new();
         for row in 0..self.rows {
             for col in 0..self.cols {
-                str.push_str(if self.image[col + row * self.cols] { "#" } else { "." });
+                str.push_str(if self.image.contains(col + row * self.cols) { "#" } else { "." });
             }
             str.push_str("\r\n");
         }
@@ -22,31 +24,32 @@ impl std::fmt::Display for Image {
 
 impl Image {
     fn new(rows: usize, cols: usize) -> Self {
-        return Image{rows: rows, cols: cols, image: vec!()};
+        return Image{rows: rows, cols: cols, image: DynBitSet::default()};
     }
 
     fn expand(&self, void: bool) -> Self {
-        let mut new_image = Image{rows: self.rows + 2, cols: self.cols + 2, image: vec![false;(self.cols + 2) * (self.rows + 2)]};
+        let mut new_image = Image{rows: self.rows + 2, cols: self.cols + 2, image: DynBitSet::with_len((self.cols + 2) * (self.rows + 2))};
 
-        self.image.iter().enumerate().for_each(|(center,bit)| {
+        // only the lit pixels need copying, the rest start out dark
+        self.image.iter().for_each(|center| {
             let col = center % self.cols;
             let row = ((center as i32 - col as i32) / self.rows as i32) as usize;
             let dest_center = (col + 1) + (row + 1) * new_image.cols;
-            new_image.image[dest_center] = *bit;
+            new_image.image.insert(dest_center);
         });
 
         // blink infinte state
         for idx in 0..new_image.cols {
-            new_image.image[idx] = void;
+            new_image.image.set(idx, void);
         }
         for idx in ((new_image.rows - 1) * new_image.cols)..new_image.image.len() {
-            new_image.image[idx] = void;
+            new_image.image.set(idx, void);
         }
         for idx in (0..(new_image.rows * new_image.cols)).step_by(new_image.cols) {
-            new_image.image[idx] = void;
+            new_image.image.set(idx, void);
         }
         for idx in (new_image.cols - 1..(new_image.rows * new_image.cols)).step_by(new_image.cols) {
-            new_image.image[idx] = void;
+            new_image.image.set(idx, void);
         }
 
         return new_image;
@@ -66,7 +69,7 @@ impl Image {
                 (row == self.rows - 1 && offset > 1 ) ||    // no rows below
                 (col == 0 && (offset == -1 || offset == -(self.cols as i32) - 1 || offset == (self.cols as i32) - 1 )) || // left side 
                 (col == self.cols - 1 && (offset == 1 || offset ==  1 - (self.cols as i32) || offset == (self.cols as i32) + 1 ))) {
-                    csi_index |= (self.image[(center as i32 + offset) as usize] as usize) << 8 - bit;
+                    csi_index |= (self.image.contains((center as i32 + offset) as usize) as usize) << 8 - bit;
             }
             else {
                 csi_index |= (default as usize) << 8 - bit
@@ -75,54 +78,81 @@ impl Image {
         return csi_index;
     }
 
-    fn enhance_image(&self, algorithm: &Vec<bool>, generation: usize) -> Image {
-        let default = if generation % 2 != 0 { algorithm[0] } else { false };
+    fn enhance_image(&self, algorithm: &DynBitSet, generation: usize) -> Image {
+        let default = if generation % 2 != 0 { algorithm.contains(0) } else { false };
         let mut new_image = self.expand(default);
-        let mut enhanced: Vec<bool> = vec![false;new_image.image.len()];
+        let mut enhanced = DynBitSet::with_len(new_image.image.len());
         for center in 0..new_image.image.len() {
             let csi_index = new_image.pixle_reading(center,default);
             // todo fix shift of center
-            enhanced[center] = algorithm[csi_index];
+            enhanced.set(center, algorithm.contains(csi_index));
         }
         new_image.image = enhanced;
         return new_image;
     }
 
     fn num_lit_pixels(&self) -> usize {
-        return self.image.iter().filter(|bit| **bit).count();
+        return self.image.count_ones();
     }
 }
 
-fn image_from_string(lines: Vec<String>) -> Image {
+fn image_from_string(section: &str) -> Image {
+    let lines: Vec<&str> = section.lines().collect();
     let rows = lines.len();
     let cols = lines[0].len();
-    let mut image: Vec<bool> = vec!();
-    lines.iter().for_each(|line| {
-        line.chars().for_each(|c| {
-            image.push(c == '#');
-        });
-    });
+    let image: DynBitSet = lines.iter().flat_map(|line| line.chars().map(|c| c == '#')).collect();
     return Image{rows,cols,image};
 }
 
-fn main() {
-    let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
+/// The image between generations, and the part 1 count once reached
+struct Enhancement {
+    image: Image,
+    lit_after_2: Option<usize>
+}
+
+impl State for Enhancement {
+    fn save(&self) -> String {
+        return format!("{}\n{}", self.lit_after_2.map_or("-".to_string(), |count| count.to_string()), self.image);
+    }
 
-    let algorithm: Vec<bool> = lines[0].chars().map(|c| c == '#').collect();
-    let mut image  = image_from_string(lines[2..].to_vec());
+    fn restore(text: &str) -> Result<Self, String> {
+        let (lit_after_2, image) = text.split_once('\n').ok_or("missing the image")?;
+        let lit_after_2 = match lit_after_2.trim() {
+            "-" => None,
+            count => Some(count.parse::<usize>().map_err(|e| e.to_string())?),
+        };
+        if image.lines().next().is_none_or(|line| line.is_empty()) {
+            return Err("missing the image".to_string());
+        }
+        return Ok(Enhancement{image: image_from_string(image), lit_after_2});
+    }
+}
 
-    for generation in 0..50 {
-        image = image.enhance_image(&algorithm,generation);
-        if generation == 1 {
-            println!("Part 1\r\n{}", "-".repeat(10));
-            println!("Lit pixel count:\r\n{}", image.num_lit_pixels());
+fn main() {
+    aoc::log::init();
+    let start = Instant::now();
+    let input = Input::from_stdin();
+    let checkpoints = Checkpoints::from_args("day-20", input.text());
+    let mut sections = input.sections();
+
+    let algorithm: DynBitSet = sections.next().unwrap().chars().map(|c| c == '#').collect();
+    let image  = image_from_string(sections.next().unwrap());
+
+    let (mut generation, mut enhancement) = checkpoints.resume().unwrap_or((0, Enhancement{image, lit_after_2: None}));
+    while generation < 50 {
+        enhancement.image = enhancement.image.enhance_image(&algorithm,generation);
+        generation += 1;
+        if generation == 2 {
+            enhancement.lit_after_2 = Some(enhancement.image.num_lit_pixels());
         }
+        checkpoints.reached(generation, &enhancement);
     }
 
+    println!("Part 1\r\n{}", "-".repeat(10));
+    println!("Lit pixel count:\r\n{}", enhancement.lit_after_2.unwrap());
+
     println!("Part 2\r\n{}", "-".repeat(10));
-    println!("Lit pixel count:\r\n{}", image.num_lit_pixels());
+    println!("Lit pixel count:\r\n{}", enhancement.image.num_lit_pixels());
   
     let duration = start.elapsed();
     println!("Total execution time: {:?}", duration);
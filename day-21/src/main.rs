@@ -1,5 +1,6 @@
-use std::io::{self, BufRead};
 use std::time::{Instant};
+use aoc::input::Input;
+use aoc::parse::{self, integer, literal, preceded, separated_pair};
 
 struct Player {
     name: String,
@@ -41,11 +42,11 @@ fn play_deterministic(player1: &mut Player, player2: &mut Player, winning_score:
         let num_spaces = roll_dice();
         if turn % 2 == 1 {
             player1.move_spaces(num_spaces);
-            println!("Player 1, rolled {}, on space: {}, score: {}, ", num_spaces, player1.current_position, player1.score);
+            aoc::trace!("Player 1, rolled {}, on space: {}, score: {}, ", num_spaces, player1.current_position, player1.score);
         }
         else {
             player2.move_spaces(num_spaces);
-            println!("Player 2, rolled {}, on space {}, score: {}, ", num_spaces, player2.current_position, player2.score)
+            aoc::trace!("Player 2, rolled {}, on space {}, score: {}, ", num_spaces, player2.current_position, player2.score);
         }
 
         if player1.score >= winning_score || player2.score >= winning_score {
@@ -58,14 +59,17 @@ fn play_deterministic(player1: &mut Player, player2: &mut Player, winning_score:
 }
 
 fn main() {
+    aoc::log::init();
     let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
-    let player1_start = lines[0].replacen("Player 1 starting position: ", "", 1).parse::<u8>().unwrap();
-    let player2_start = lines[1].replacen("Player 2 starting position: ", "", 1).parse::<u8>().unwrap();
+    let input = Input::from_stdin();
+    // Player 1 starting position: 4
+    let player = separated_pair(preceded(literal("Player "), integer::<u8>()), literal(" starting position: "), integer::<u8>());
+    let starts = parse::parse(parse::lines(player), input.text()).unwrap();
+    let (player1_num, player1_start) = starts[0];
+    let (player2_num, player2_start) = starts[1];
 
-    let mut player1 = Player{name: "Player 1".to_string(), start_at: player1_start, current_position: player1_start, score: 0};
-    let mut player2 = Player{name: "Player 2".to_string(), start_at: player2_start, current_position: player2_start, score: 0};
+    let mut player1 = Player{name: format!("Player {}", player1_num), start_at: player1_start, current_position: player1_start, score: 0};
+    let mut player2 = Player{name: format!("Player {}", player2_num), start_at: player2_start, current_position: player2_start, score: 0};
 
     let num_dice_rolls = play_deterministic(&mut player1, &mut player2, 1000);
     println!("Part 1\r\n{}", "-".repeat(10));
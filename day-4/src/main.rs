@@ -1,51 +1,37 @@
-use std::io::{self, BufRead};
 use std::collections::HashMap;
 use std::collections::LinkedList;
 use std::time::{Duration, Instant};
+use aoc::bitset::BitSet;
+use aoc::input::Input;
+
+type Marks = BitSet<25>;
 
 #[derive(Debug,Clone)]
 struct BingoBoard {
     board_num: usize,
     numbers: HashMap<i16, usize>,
-    called: u32
+    called: Marks
 }
 
 impl BingoBoard {
-    fn from_stdin(stdin: &std::io::Stdin, board_num: usize) -> Option<BingoBoard> {
-        let mut new_board = BingoBoard { board_num: board_num, numbers: HashMap::with_capacity(25), called: 0 };
-        stdin.lock().lines().skip(1);
+    fn from_section(section: &str, board_num: usize) -> BingoBoard {
+        let mut new_board = BingoBoard { board_num: board_num, numbers: HashMap::with_capacity(25), called: Marks::new() };
         // fill board
-        for row in 0..6 {
-            let mut line: String = String::new();
-            if stdin.read_line(&mut line).expect("stdin error") == 0 {
-                return None;
-            }
-            line.split(" ").flat_map(|val| val.trim().parse::<i16>()).enumerate().for_each(|(i,val)| {
-                new_board.numbers.insert(val, (row - 1) * 5 + i);
+        section.lines().enumerate().for_each(|(row, line)| {
+            line.split_whitespace().flat_map(|val| val.parse::<i16>()).enumerate().for_each(|(i,val)| {
+                new_board.numbers.insert(val, row * 5 + i);
             });
-        }
-        return Some(new_board);
+        });
+        return new_board;
     }
 
 
     fn call_number(&mut self, number: i16) -> bool {
         if let Some(val) = self.numbers.get(&number) {
-            self.called |= 1 << val;
+            self.called.insert(*val);
         }
-        // win condition
-        if self.called & 0x1F == 0x1F            || // row 1
-           self.called & 0x3E0 == 0x3E0          || // row 2
-           self.called & 0x7C00 == 0x7C00        || // row 3
-           self.called & 0xF8000 == 0xF8000      || // row 4
-           self.called & 0x1F00000 == 0x1F00000  || // row 5
-           self.called & 0x1084210 == 0x1084210  || // col 5
-           self.called & 0x842108 == 0x842108    || // col 4
-           self.called & 0x421084 == 0x421084    || // col 3
-           self.called & 0x210842 == 0x210842    || // col 2
-           self.called & 0x108421 == 0x108421    {  // col 1
-            return true;
-        }
-        return false;
+        // win condition, any full row or column
+        return (0..5).any(|i| Marks::row(i, 5).is_subset(&self.called) || Marks::column(i, 5, 5).is_subset(&self.called));
     }
 
     fn score(&self) -> i32 {
@@ -55,12 +41,9 @@ impl BingoBoard {
         for (number, index) in self.numbers.iter() {
             idx_numbers[(*index as usize)] = *number;
         }
-        // iterate bits, score those not set
-        for bit_index in 0..25 {
-            let mask = 1 << bit_index;
-            if self.called & mask == 0 {
-                score += idx_numbers[bit_index] as i32;
-            }
+        // score those not called
+        for bit_index in (!self.called).iter() {
+            score += idx_numbers[bit_index] as i32;
         }
         return score;
     }
@@ -81,13 +64,11 @@ fn first_winning_board(numbers: &mut LinkedList<i16>,boards: &mut Vec<BingoBoard
 
 fn main() {
     let start = Instant::now();
-    let stdin = io::stdin();
-    let mut numbers: LinkedList<i16> = stdin.lock().lines().next().unwrap().unwrap().split(",").flat_map(|s| s.trim().parse::<i16>()).collect();
+    let input = Input::from_stdin();
+    let mut sections = input.sections();
+    let mut numbers: LinkedList<i16> = sections.next().unwrap().split(",").flat_map(|s| s.trim().parse::<i16>()).collect();
 
-    let mut boards: Vec<BingoBoard> = vec!();
-    while let Some(board) = BingoBoard::from_stdin(&stdin, boards.len() + 1) {
-        boards.push(board);
-    }
+    let mut boards: Vec<BingoBoard> = sections.enumerate().map(|(i, section)| BingoBoard::from_section(section, i + 1)).collect();
 
     let mut winners: Vec<(usize,usize,i32,i16)> = vec!();
     let mut first: bool = true;
@@ -1,30 +1,65 @@
-use std::io::{self, BufRead};
 use std::time::{Duration, Instant};
+use aoc::checkpoint::{Checkpoints, State};
+use aoc::input::Input;
+use aoc::parse::{self, integer, literal, separated};
 
-fn num_fish_by_days(num_days: usize, fish_by_days: &mut [i64;9]) -> i64 {
-    for day in 1..num_days+1 {
-        fish_by_days.rotate_left(1);
-        fish_by_days[6] += fish_by_days[8];
+/// Fish counted by days left until they spawn, and the part 1 answer once reached
+struct School {
+    fish_by_days: [i64;9],
+    after_80: Option<i64>
+}
+
+impl State for School {
+    fn save(&self) -> String {
+        let counts: Vec<String> = self.fish_by_days.iter().map(|count| count.to_string()).collect();
+        return format!("{}\n{}\n", counts.join(" "), self.after_80.map_or("-".to_string(), |count| count.to_string()));
+    }
+
+    fn restore(text: &str) -> Result<Self, String> {
+        let (counts, after_80) = text.split_once('\n').ok_or("missing the part 1 answer")?;
+        let counts: Vec<i64> = parse::parse(separated(integer(), literal(" ")), counts).map_err(|e| e.to_string())?;
+        let fish_by_days: [i64;9] = counts.try_into().map_err(|_| "expected 9 fish counts")?;
+        let after_80 = match after_80.trim() {
+            "-" => None,
+            count => Some(count.parse::<i64>().map_err(|e| e.to_string())?),
+        };
+        return Ok(School{fish_by_days, after_80});
     }
-    return fish_by_days.iter().sum::<i64>();
+}
+
+fn next_day(fish_by_days: &mut [i64;9]) {
+    fish_by_days.rotate_left(1);
+    fish_by_days[6] += fish_by_days[8];
 }
 
 fn main() {
+    aoc::log::init();
     let start = Instant::now();
-    let stdin = io::stdin();
+    let input = Input::from_stdin();
+    let checkpoints = Checkpoints::from_args("day-6", input.text());
 
     let mut fish_by_days: [i64;9] = [0;9];
-    stdin.lock().lines().flatten().for_each(|line| {
+    input.lines().for_each(|line| {
         line.split(",").flat_map(|s| s.trim().parse::<usize>()).for_each(|fish_day| {
             fish_by_days[fish_day] += 1; 
         });
     });
 
+    let (mut day, mut school) = checkpoints.resume().unwrap_or((0, School{fish_by_days, after_80: None}));
+    while day < 256 {
+        next_day(&mut school.fish_by_days);
+        day += 1;
+        if day == 80 {
+            school.after_80 = Some(school.fish_by_days.iter().sum::<i64>());
+        }
+        checkpoints.reached(day, &school);
+    }
+
     println!("Part 1\r\n{}", "-".repeat(10));
-    println!("Number fish after {} days: {}\r\n", 80, num_fish_by_days(80,&mut fish_by_days));
+    println!("Number fish after {} days: {}\r\n", 80, school.after_80.unwrap());
 
     println!("Part 2\r\n{}", "-".repeat(10));
-    println!("Number fish after {} days: {}\r\n", 256, num_fish_by_days(256 - 80,&mut fish_by_days));
+    println!("Number fish after {} days: {}\r\n", 256, school.fish_by_days.iter().sum::<i64>());
 
     let duration = start.elapsed();
     println!("Total execution time: {:?}", duration);
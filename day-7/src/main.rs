@@ -1,12 +1,12 @@
-use std::io::{self, BufRead};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
+use aoc::input::Input;
 
 
 fn main() {
     let start = Instant::now();
-    let stdin = io::stdin();
-    let mut numbers: Vec<i32> = stdin.lock().lines().next().unwrap().unwrap().split(",").flat_map(|s| s.trim().parse::<i32>()).collect();
+    let input = Input::from_stdin();
+    let mut numbers: Vec<i32> = input.first_line().split(",").flat_map(|s| s.trim().parse::<i32>()).collect();
 
     // median
     numbers.sort();
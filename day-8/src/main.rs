@@ -1,24 +1,18 @@
-use std::io::{self, BufRead};
 use std::time::{Duration, Instant};
+use aoc::bitset::BitSet;
+use aoc::input::Input;
 
-fn signal_to_bits(signal: &str) -> u8 {
-    let mut bits: u8 = 0;
-    for c in signal.chars() {
-        match c {
-            'a' => bits |= 0b0000001,
-            'b' => bits |= 0b0000010,
-            'c' => bits |= 0b0000100,
-            'd' => bits |= 0b0001000,
-            'e' => bits |= 0b0010000,
-            'f' => bits |= 0b0100000,
-            'g' => bits |= 0b1000000,
-             _  => unreachable!()
-        }
-    }
-    return bits;
+/// Lit segments, a through g
+type Segments = BitSet<7>;
+
+fn signal_to_bits(signal: &str) -> Segments {
+    return signal.chars().map(|c| match c {
+        'a'..='g' => (c as u8 - b'a') as usize,
+         _  => unreachable!()
+    }).collect();
 }
 
-fn count_unique_digits(line: &String, digits_counts: &mut [i32;7]) -> i32 {
+fn count_unique_digits(line: &str, digits_counts: &mut [i32;7]) -> i32 {
     let mut wires = line.split("|");
     let signals: Vec<&str> = wires.next().unwrap().split(" ").filter(|val| val.len() > 0).collect::<Vec<&str>>();
     let digits: Vec<&str> = wires.next().unwrap().split(" ").filter(|val| val.len() > 0).collect::<Vec<&str>>();
@@ -34,12 +28,12 @@ fn count_unique_digits(line: &String, digits_counts: &mut [i32;7]) -> i32 {
 }
 
 
-fn map_segments_to_output(line: &String) -> i32 {
+fn map_segments_to_output(line: &str) -> i32 {
     let mut wires = line.split("|");
     let signals: Vec<&str> = wires.next().unwrap().split(" ").filter(|val| val.len() > 0).collect::<Vec<&str>>();
     let digits: Vec<&str> = wires.next().unwrap().split(" ").filter(|val| val.len() > 0).collect::<Vec<&str>>();
 
-    let mut segment_map: [u8;10] = [0;10];
+    let mut segment_map: [Segments;10] = [Segments::new();10];
     let unique_signals: Vec<&str> = signals.iter().filter(|signal| signal.len() >= 2 && signal.len() <= 4 || signal.len() == 7).map(|s| s.as_ref()).collect();
     let other_signals: Vec<&str> = signals.iter().filter(|signal| !(signal.len() >= 2 && signal.len() <= 4 || signal.len() == 7)).map(|s| s.as_ref()).collect();
     // Unique signals
@@ -84,7 +78,7 @@ fn map_segments_to_output(line: &String) -> i32 {
             _ => unreachable!()
         }
     }
-    assert_eq!(segment_map.into_iter().filter(|&has_bits| has_bits > 0).count(),10);
+    assert_eq!(segment_map.into_iter().filter(|has_bits| !has_bits.is_empty()).count(),10);
 
     // map our digits from segments, shifting digits by 10's place
     let output: i32 = digits.iter().map(|digit| signal_to_bits(digit)).enumerate().map(|(idx,bits)| {
@@ -96,15 +90,15 @@ fn map_segments_to_output(line: &String) -> i32 {
 
 fn main() {
     let start = Instant::now();
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin.lock().lines().flatten().collect();
+    let input = Input::from_stdin();
+    let lines: Vec<&str> = input.lines().collect();
 
     let mut counts: [i32;7] = [0;7];
     let mut unique_total: i32 = 0;
     let mut output_total: i32 = 0;
     lines.into_iter().for_each(|line| {
-        unique_total += count_unique_digits(&line, &mut counts);
-        output_total += map_segments_to_output(&line);
+        unique_total += count_unique_digits(line, &mut counts);
+        output_total += map_segments_to_output(line);
     });
 
     println!("Part 1\r\n{}", "-".repeat(10));
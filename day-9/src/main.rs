@@ -1,13 +1,15 @@
-use std::io::{self, BufRead, Write, stdout};
+use std::io::{Write, stdout};
 use std::time::{Instant};
 use std::thread::{sleep_ms};
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 use std::env;
 use crossterm::{
     ExecutableCommand, QueueableCommand,
     terminal, cursor, style::{self, Stylize, Color}, Result
 };
 use structopt::StructOpt;
+use aoc::graph;
+use aoc::input::Input;
 
 
 /// Commandline arguments
@@ -25,10 +27,6 @@ fn mark_seen(seen: &mut HashMap<(usize, usize),char>,x: usize,y: usize, basin_nu
     };
 }
 
-fn not_seen(seen: &mut HashMap<(usize, usize),char>,x: usize,y: usize) -> bool {
-    return !(seen.contains_key(&(x,y)));
-}
-
 fn render_map(map: &Vec<Vec<char>>, seen: &mut HashMap<(usize, usize),char>, delay: u32) {
     let mut stdout = stdout();
     let get_level = |x: usize,y: usize| -> i32 { map[x][y].to_string().parse::<i32>().unwrap() };
@@ -82,8 +80,8 @@ fn main() {
 
 
     let start = Instant::now();
-    let stdin = io::stdin();
-    let map: Vec<Vec<char>> = stdin.lock().lines().map(|line| line.unwrap().chars().collect::<Vec<char>>()).collect();
+    let input = Input::from_stdin();
+    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect::<Vec<char>>()).collect();
 
     let width:  usize = map[0].len();
     let height: usize = map.len();
@@ -105,49 +103,34 @@ fn main() {
     let risk_level: i32 = low_points.iter().map(|(_x,_y,level)| level + 1).sum();
 
     let mut seen: HashMap<(usize, usize),char> = HashMap::new();
-    let mut basin_points: Vec<i32> = vec!();
+    let mut basin_points: Vec<i32> = vec![0; low_points.len()];
     let get_level = |x: usize,y: usize| -> i32 { map[x][y].to_string().parse::<i32>().unwrap() };
 
-    // from low points flood-fill
-    let mut fill: VecDeque<(usize, usize,usize)> = VecDeque::new();
-    low_points.into_iter().enumerate().for_each(|(basin_num,(x,y,_level))| {
-        fill.push_back((x,y,basin_num));
-        basin_points.push(0);
+    // from low points flood-fill, every basin at once, 9s being the walls between them
+    let basin_starts: Vec<usize> = low_points.iter().map(|(x,y,_level)| x * width + y).collect();
+    let fill = graph::bfs(width * height, basin_starts, |v| {
+        graph::grid_neighbors(v, height, width).filter(|n| get_level(n / width, n % width) != 9)
     });
 
     let mut stdout = stdout();
     stdout.execute(terminal::Clear(terminal::ClearType::All));
     let mut counter = 0;
-    while fill.len() > 0 {
-        let (x, y, basin_num) = fill.pop_front().unwrap();
-        if not_seen(&mut seen,x,y) {
-            basin_points[basin_num as usize] += 1; // add to current basin
-            mark_seen(&mut seen,x,y, Some(basin_num)); // mark it seen
-
-            if visualize && (counter >= refresh || fill.len() == 0) {
-                render_map(&map, &mut seen, delay);
-                counter = 0;
-            }
-            else {
-                counter += 1;
-            }
-
-            // start walking in all directions
-            if x > 0 && not_seen(&mut seen,x - 1,y) && get_level(x - 1,y) != 9 {
-                fill.push_back((x - 1,y,basin_num)); // queue walk north
-            }
-            if x < height - 1 && not_seen(&mut seen,x + 1,y) && get_level(x + 1,y) != 9 {
-                fill.push_back((x + 1,y,basin_num)); // queue walk south
-            }
-            if y > 0 && not_seen(&mut seen,x,y - 1) && get_level(x,y - 1) != 9 {
-                fill.push_back((x,y - 1,basin_num)); // queue walk west
-            }
-            if y < width - 1 && not_seen(&mut seen,x,y + 1) && get_level(x,y + 1) != 9 {
-                fill.push_back((x,y + 1,basin_num)); // queue walk east
-            }
-
+    for visit in fill {
+        let (x, y, basin_num) = (visit.node / width, visit.node % width, visit.start);
+        basin_points[basin_num] += 1; // add to current basin
+        mark_seen(&mut seen,x,y, Some(basin_num)); // mark it seen
+
+        if visualize && counter >= refresh {
+            render_map(&map, &mut seen, delay);
+            counter = 0;
+        }
+        else {
+            counter += 1;
         }
     }
+    if visualize {
+        render_map(&map, &mut seen, delay);
+    }
 
     basin_points.sort();
     basin_points.reverse();